@@ -1,22 +1,66 @@
+mod output;
+
 use anyhow::anyhow;
 use ethabi::{
 	decode, encode,
 	param_type::{ParamType, Reader},
 	token::{LenientTokenizer, StrictTokenizer, Token, Tokenizer},
-	Contract, Event, Function, Hash,
+	AbiError, Artifact, Contract, Event, EventParam, Function, Hash, Log, Param, RawLog, RawTopicFilter, Topic,
 };
 use itertools::Itertools;
+use output::{Decoded, Format, Output};
+use serde_json::{json, Value};
 use sha3::{Digest, Keccak256};
-use std::fs::File;
+use std::{
+	fs::File,
+	io::{self, BufRead, Write},
+	path::Path,
+};
 use structopt::StructOpt;
 
 #[derive(StructOpt, Debug)]
 /// Ethereum ABI coder.
-enum Opt {
+struct Opt {
+	/// Output format, `text` or `json`.
+	#[structopt(long, global = true, default_value = "text")]
+	output: Format,
+	#[structopt(subcommand)]
+	command: Command,
+}
+
+#[derive(StructOpt, Debug)]
+enum Command {
 	/// Encode ABI call.
 	Encode(Encode),
 	/// Decode ABI call result.
 	Decode(Decode),
+	/// Compute selectors and topics, and normalize signatures.
+	Sig(Sig),
+	/// Inspect JSON ABI files.
+	Abi(Abi),
+	/// Build the `topics` of an `eth_getLogs` filter for an event from JSON ABI file.
+	Filter {
+		abi_path: String,
+		event_name_or_signature: String,
+		/// Value of an indexed param as `name=value`, or `name=value1,value2,..` to match any of the values.
+		/// Values of dynamic types are hashed.
+		#[structopt(short, long = "topic", name = "name=value", number_of_values = 1)]
+		topics: Vec<String>,
+		/// Allow short representation of values.
+		#[structopt(short, long)]
+		lenient: bool,
+	},
+	/// Decode newline delimited json requests from stdin, writing a json result line for each.
+	///
+	/// Requests are `{"calldata": "0x.."}`, `{"function": "name or signature", "output": "0x.."}` or logs
+	/// `{"topics": [..], "data": "0x.."}`, as returned by `eth_getLogs`. An `id` field is copied to the result.
+	///
+	/// Results are always json, whatever `--output` is, which only changes how errors loading the ABIs are printed.
+	Batch {
+		/// JSON ABI file, may be given more than once.
+		#[structopt(short, long = "abi", name = "abi-path", number_of_values = 1, required = true)]
+		abi_paths: Vec<String>,
+	},
 }
 
 #[derive(StructOpt, Debug)]
@@ -42,12 +86,47 @@ enum Encode {
 		#[structopt(short, long)]
 		lenient: bool,
 	},
+	/// Load custom error from JSON ABI file, `Error(string)` and `Panic(uint256)` are always known.
+	Error {
+		abi_path: String,
+		error_name_or_signature: String,
+		#[structopt(short, number_of_values = 1)]
+		params: Vec<String>,
+		/// Allow short representation of input params.
+		#[structopt(short, long)]
+		lenient: bool,
+	},
+	/// Load constructor from JSON ABI or artifact file.
+	///
+	/// Encodes deployment data, the bytecode followed by the arguments, if the bytecode is given or found in the
+	/// artifact, and just the arguments otherwise.
+	Constructor {
+		abi_path: String,
+		/// Contract bytecode, as hex or as a file containing hex.
+		#[structopt(long)]
+		bytecode: Option<String>,
+		/// Encode the arguments only, as needed to verify a contract, even if the bytecode is known.
+		#[structopt(long, conflicts_with = "bytecode")]
+		args_only: bool,
+		#[structopt(short, number_of_values = 1)]
+		params: Vec<String>,
+		/// Allow short representation of input params.
+		#[structopt(short, long)]
+		lenient: bool,
+	},
 }
 
 #[derive(StructOpt, Debug)]
 enum Decode {
 	/// Load function from JSON ABI file.
-	Function { abi_path: String, function_name_or_signature: String, data: String },
+	Function {
+		abi_path: String,
+		function_name_or_signature: String,
+		data: String,
+		/// Decode call data, starting with the function selector, instead of the call result.
+		#[structopt(short, long)]
+		input: bool,
+	},
 	/// Specify types of input params inline.
 	Params {
 		#[structopt(short, name = "type", number_of_values = 1)]
@@ -62,39 +141,115 @@ enum Decode {
 		topics: Vec<String>,
 		data: String,
 	},
+	/// Decode call data, finding the function by its selector.
+	Calldata { abi_path: String, data: String },
+	/// Decode revert data, finding the custom error by its selector. `Error(string)` and `Panic(uint256)` are
+	/// always known.
+	Error { abi_path: String, data: String },
+	/// Decode event logs, finding events by topic0 or, if anonymous, by the number of topics.
+	Logs {
+		/// JSON ABI file to look up events in, may be given more than once.
+		#[structopt(short, long = "abi", name = "abi-path", number_of_values = 1, required = true)]
+		abi_paths: Vec<String>,
+		#[structopt(short = "l", name = "topic", number_of_values = 1)]
+		topics: Vec<String>,
+		/// Read logs from an `eth_getLogs` result or a transaction receipt in JSON, `-` for stdin.
+		#[structopt(long, name = "json-path", conflicts_with_all = &["topic", "data"])]
+		json: Option<String>,
+		#[structopt(required_unless = "json-path")]
+		data: Option<String>,
+	},
 }
 
-fn main() -> anyhow::Result<()> {
-	println!("{}", execute(std::env::args())?);
+#[derive(StructOpt, Debug)]
+enum Sig {
+	/// Compute the 4 byte selector of a function or error signature.
+	Selector { signature: String },
+	/// Compute the topic0 of an event signature.
+	Topic { signature: String },
+	/// List the selectors of functions and errors, and the topics of events, of a JSON ABI file.
+	List { abi_path: String },
+	/// Normalize a signature, e.g. `transfer(address to, uint amount)` to `transfer(address,uint256)`.
+	Normalize { signature: String },
+}
 
-	Ok(())
+#[derive(StructOpt, Debug)]
+enum Abi {
+	/// Show the constructor, functions, events, errors and receive/fallback of a JSON ABI file, with their
+	/// selectors and topics.
+	Show { abi_path: String },
 }
 
-fn execute<I>(args: I) -> anyhow::Result<String>
-where
-	I: IntoIterator,
-	I::Item: Into<std::ffi::OsString> + Clone,
-{
-	let opt = Opt::from_iter(args);
+fn main() {
+	let opt = Opt::from_args();
+	if let Err(err) = run(opt.command, opt.output, &mut io::stdout().lock()) {
+		eprintln!("{}", opt.output.error(&err));
+		std::process::exit(1);
+	}
+}
 
-	match opt {
-		Opt::Encode(Encode::Function { abi_path, function_name_or_signature, params, lenient }) => {
+fn run(command: Command, format: Format, out: &mut dyn Write) -> anyhow::Result<()> {
+	let output = match command {
+		Command::Encode(Encode::Function { abi_path, function_name_or_signature, params, lenient }) => {
 			encode_input(&abi_path, &function_name_or_signature, &params, lenient)
 		}
-		Opt::Encode(Encode::Params { params, lenient }) => encode_params(&params, lenient),
-		Opt::Decode(Decode::Function { abi_path, function_name_or_signature, data }) => {
+		Command::Encode(Encode::Params { params, lenient }) => encode_params(&params, lenient),
+		Command::Encode(Encode::Error { abi_path, error_name_or_signature, params, lenient }) => {
+			encode_error(&abi_path, &error_name_or_signature, &params, lenient)
+		}
+		Command::Encode(Encode::Constructor { abi_path, bytecode, args_only, params, lenient }) => {
+			encode_constructor(&abi_path, bytecode.as_deref(), args_only, &params, lenient)
+		}
+		Command::Decode(Decode::Function { abi_path, function_name_or_signature, data, input: false }) => {
 			decode_call_output(&abi_path, &function_name_or_signature, &data)
 		}
-		Opt::Decode(Decode::Params { types, data }) => decode_params(&types, &data),
-		Opt::Decode(Decode::Log { abi_path, event_name_or_signature, topics, data }) => {
+		Command::Decode(Decode::Function { abi_path, function_name_or_signature, data, input: true }) => {
+			decode_call_input(&abi_path, &function_name_or_signature, &data)
+		}
+		Command::Decode(Decode::Params { types, data }) => decode_params(&types, &data),
+		Command::Decode(Decode::Log { abi_path, event_name_or_signature, topics, data }) => {
 			decode_log(&abi_path, &event_name_or_signature, &topics, &data)
 		}
-	}
+		Command::Decode(Decode::Calldata { abi_path, data }) => decode_calldata(&abi_path, &data),
+		Command::Decode(Decode::Error { abi_path, data }) => decode_error(&abi_path, &data),
+		Command::Decode(Decode::Logs { abi_paths, topics, json, data }) => {
+			decode_logs(&abi_paths, &topics, json.as_deref(), data.as_deref())
+		}
+		Command::Sig(Sig::Selector { signature }) => {
+			let selector = hash_signature(&normalize_signature(&signature)?);
+			Ok(Output::Field("selector", format!("0x{}", hex::encode(&selector[..4]))))
+		}
+		Command::Sig(Sig::Topic { signature }) => {
+			let topic = hash_signature(&normalize_signature(&signature)?);
+			Ok(Output::Field("topic0", format!("0x{topic:x}")))
+		}
+		Command::Filter { abi_path, event_name_or_signature, topics, lenient } => {
+			event_filter(&abi_path, &event_name_or_signature, &topics, lenient)
+		}
+		Command::Sig(Sig::List { abi_path }) => list_signatures(&abi_path),
+		Command::Sig(Sig::Normalize { signature }) => Ok(Output::Field("signature", normalize_signature(&signature)?)),
+		Command::Abi(Abi::Show { abi_path }) => Ok(Output::Abi(load_contract(&abi_path)?)),
+		Command::Batch { abi_paths } => return batch(&abi_paths, &mut io::stdin().lock(), out),
+	}?;
+	writeln!(out, "{}", output.render(format))?;
+	Ok(())
 }
 
-fn load_function(path: &str, name_or_signature: &str) -> anyhow::Result<Function> {
+fn load_contract(path: &str) -> anyhow::Result<Contract> {
 	let file = File::open(path)?;
-	let contract = Contract::load(file)?;
+	Ok(Contract::load(file)?)
+}
+
+fn load_contracts(paths: &[String]) -> anyhow::Result<Vec<Contract>> {
+	paths.iter().map(|path| load_contract(path)).collect()
+}
+
+fn load_function(path: &str, name_or_signature: &str) -> anyhow::Result<Function> {
+	let contract = load_contract(path)?;
+	find_function(&contract, name_or_signature).cloned()
+}
+
+fn find_function<'a>(contract: &'a Contract, name_or_signature: &str) -> anyhow::Result<&'a Function> {
 	let params_start = name_or_signature.find('(');
 
 	match params_start {
@@ -106,7 +261,6 @@ fn load_function(path: &str, name_or_signature: &str) -> anyhow::Result<Function
 				.functions_by_name(name)?
 				.iter()
 				.find(|f| f.signature() == name_or_signature)
-				.cloned()
 				.ok_or_else(|| anyhow!("invalid function signature `{}`", name_or_signature))
 		}
 
@@ -115,7 +269,7 @@ fn load_function(path: &str, name_or_signature: &str) -> anyhow::Result<Function
 			let functions = contract.functions_by_name(name_or_signature)?;
 			match functions.len() {
 				0 => unreachable!(),
-				1 => Ok(functions[0].clone()),
+				1 => Ok(&functions[0]),
 				_ => Err(anyhow!(
 					"More than one function found for name `{}`, try providing the full signature",
 					name_or_signature
@@ -126,8 +280,7 @@ fn load_function(path: &str, name_or_signature: &str) -> anyhow::Result<Function
 }
 
 fn load_event(path: &str, name_or_signature: &str) -> anyhow::Result<Event> {
-	let file = File::open(path)?;
-	let contract = Contract::load(file)?;
+	let contract = load_contract(path)?;
 	let params_start = name_or_signature.find('(');
 
 	match params_start {
@@ -158,6 +311,42 @@ fn load_event(path: &str, name_or_signature: &str) -> anyhow::Result<Event> {
 	}
 }
 
+/// Errors which Solidity reverts with without them being declared in the ABI.
+fn builtin_errors() -> [AbiError; 2] {
+	let error = |name: &str, param: &str, kind| AbiError {
+		name: name.to_owned(),
+		inputs: vec![Param { name: param.to_owned(), kind, internal_type: None, components: Vec::new() }],
+	};
+	[error("Error", "message", ParamType::String), error("Panic", "code", ParamType::Uint(256))]
+}
+
+fn load_error(path: &str, name_or_signature: &str) -> anyhow::Result<AbiError> {
+	let contract = load_contract(path)?;
+	let name = name_or_signature.split('(').next().unwrap_or_default();
+	let is_signature = name.len() != name_or_signature.len();
+
+	let declared = contract.errors_by_name(name).map(|errors| errors.to_vec()).unwrap_or_default();
+	let candidates = match declared.is_empty() {
+		true => builtin_errors().into_iter().filter(|error| error.name == name).collect(),
+		false => declared,
+	};
+
+	match is_signature {
+		true => candidates
+			.into_iter()
+			.find(|error| error.canonical_signature() == name_or_signature)
+			.ok_or_else(|| anyhow!("invalid error signature `{}`", name_or_signature)),
+		false => match candidates.len() {
+			0 => Err(anyhow!("No error named `{}` found", name)),
+			1 => Ok(candidates.into_iter().next().expect("one candidate")),
+			_ => Err(anyhow!(
+				"More than one error found for name `{}`, try providing the full signature",
+				name_or_signature
+			)),
+		},
+	}
+}
+
 fn parse_tokens(params: &[(ParamType, &str)], lenient: bool) -> anyhow::Result<Vec<Token>> {
 	params
 		.iter()
@@ -169,7 +358,7 @@ fn parse_tokens(params: &[(ParamType, &str)], lenient: bool) -> anyhow::Result<V
 		.map_err(From::from)
 }
 
-fn encode_input(path: &str, name_or_signature: &str, values: &[String], lenient: bool) -> anyhow::Result<String> {
+fn encode_input(path: &str, name_or_signature: &str, values: &[String], lenient: bool) -> anyhow::Result<Output> {
 	let function = load_function(path, name_or_signature)?;
 
 	let params: Vec<_> =
@@ -178,10 +367,10 @@ fn encode_input(path: &str, name_or_signature: &str, values: &[String], lenient:
 	let tokens = parse_tokens(&params, lenient)?;
 	let result = function.encode_input(&tokens)?;
 
-	Ok(hex::encode(result))
+	Ok(Output::Encoded(result))
 }
 
-fn encode_params(params: &[String], lenient: bool) -> anyhow::Result<String> {
+fn encode_params(params: &[String], lenient: bool) -> anyhow::Result<Output> {
 	assert_eq!(params.len() % 2, 0);
 
 	let params = params
@@ -193,24 +382,97 @@ fn encode_params(params: &[String], lenient: bool) -> anyhow::Result<String> {
 	let tokens = parse_tokens(params.as_slice(), lenient)?;
 	let result = encode(&tokens);
 
-	Ok(hex::encode(result))
+	Ok(Output::Encoded(result))
+}
+
+fn encode_error(path: &str, name_or_signature: &str, values: &[String], lenient: bool) -> anyhow::Result<Output> {
+	let error = load_error(path, name_or_signature)?;
+
+	let params: Vec<_> =
+		error.inputs.iter().map(|param| param.kind.clone()).zip(values.iter().map(|v| v as &str)).collect();
+
+	let tokens = parse_tokens(&params, lenient)?;
+	let result = error.encode(&tokens)?;
+
+	Ok(Output::Encoded(result))
+}
+
+fn encode_constructor(
+	path: &str,
+	bytecode: Option<&str>,
+	args_only: bool,
+	values: &[String],
+	lenient: bool,
+) -> anyhow::Result<Output> {
+	let artifact = Artifact::load(File::open(path)?)?;
+	let inputs = artifact.abi.constructor().map(|constructor| &constructor.inputs[..]).unwrap_or_default();
+	if inputs.len() != values.len() {
+		return Err(anyhow!("Constructor takes {} params, {} given", inputs.len(), values.len()));
+	}
+
+	let params: Vec<_> = inputs.iter().map(|param| param.kind.clone()).zip(values.iter().map(|v| v as &str)).collect();
+	let tokens = parse_tokens(&params, lenient)?;
+
+	let code = match (args_only, bytecode) {
+		(true, _) => Vec::new(),
+		(false, Some(bytecode)) => read_bytecode(bytecode)?,
+		(false, None) => artifact.bytecode.ok_or_else(|| {
+			anyhow!(
+				"No bytecode in {}, which may be a plain ABI, unlinked or abstract; give `--bytecode`, or `--args-only` \
+				 to encode the arguments only",
+				path
+			)
+		})?,
+	};
+	let result = match artifact.abi.constructor() {
+		Some(constructor) => constructor.encode_input(code, &tokens)?,
+		// Without a constructor there are no arguments.
+		None => code,
+	};
+
+	Ok(Output::Encoded(result))
 }
 
-fn decode_call_output(path: &str, name_or_signature: &str, data: &str) -> anyhow::Result<String> {
+/// Reads bytecode given as hex or as a file containing hex.
+fn read_bytecode(bytecode: &str) -> anyhow::Result<Vec<u8>> {
+	match Path::new(bytecode).is_file() {
+		true => Ok(decode_hex(std::fs::read_to_string(bytecode)?.trim())?),
+		false => Ok(decode_hex(bytecode)?),
+	}
+}
+
+fn decode_call_output(path: &str, name_or_signature: &str, data: &str) -> anyhow::Result<Output> {
 	let function = load_function(path, name_or_signature)?;
 	let data: Vec<u8> = hex::decode(data)?;
-	let tokens = function.decode_output(&data)?;
-	let types = function.outputs;
+	decode_output(&function, &data)
+}
 
-	assert_eq!(types.len(), tokens.len());
+fn decode_output(function: &Function, data: &[u8]) -> anyhow::Result<Output> {
+	let tokens = function.decode_output(data)?;
 
-	let result =
-		types.iter().zip(tokens.iter()).map(|(ty, to)| format!("{} {to}", ty.kind)).collect::<Vec<String>>().join("\n");
+	assert_eq!(function.outputs.len(), tokens.len());
 
-	Ok(result)
+	Ok(Output::Decoded { signature: None, params: decoded_params(&function.outputs, tokens), typed: true })
 }
 
-fn decode_params(types: &[String], data: &str) -> anyhow::Result<String> {
+fn decode_call_input(path: &str, name_or_signature: &str, data: &str) -> anyhow::Result<Output> {
+	let function = load_function(path, name_or_signature)?;
+	let data = decode_hex(data)?;
+	let selector = function.short_signature();
+	if !data.starts_with(&selector) {
+		return Err(anyhow!(
+			"Call data selector 0x{} does not match `{}` (0x{})",
+			hex::encode(&data[..data.len().min(4)]),
+			function.canonical_signature(),
+			hex::encode(selector)
+		));
+	}
+	let tokens = function.decode_input(&data[4..])?;
+
+	Ok(Output::Decoded { signature: None, params: decoded_params(&function.inputs, tokens), typed: false })
+}
+
+fn decode_params(types: &[String], data: &str) -> anyhow::Result<Output> {
 	let types: Vec<ParamType> = types.iter().map(|s| Reader::read(s)).collect::<Result<_, _>>()?;
 
 	let data: Vec<u8> = hex::decode(data)?;
@@ -219,26 +481,482 @@ fn decode_params(types: &[String], data: &str) -> anyhow::Result<String> {
 
 	assert_eq!(types.len(), tokens.len());
 
-	let result =
-		types.iter().zip(tokens.iter()).map(|(ty, to)| format!("{ty} {to}")).collect::<Vec<String>>().join("\n");
+	let params = types.into_iter().zip(tokens).map(|(kind, token)| Decoded::unnamed(kind, token)).collect();
 
-	Ok(result)
+	Ok(Output::Decoded { signature: None, params, typed: true })
 }
 
-fn decode_log(path: &str, name_or_signature: &str, topics: &[String], data: &str) -> anyhow::Result<String> {
+fn decode_log(path: &str, name_or_signature: &str, topics: &[String], data: &str) -> anyhow::Result<Output> {
 	let event = load_event(path, name_or_signature)?;
 	let topics: Vec<Hash> = topics.iter().map(|t| t.parse()).collect::<Result<_, _>>()?;
 	let data = hex::decode(data)?;
 	let decoded = event.parse_log((topics, data).into())?;
 
-	let result = decoded
-		.params
-		.into_iter()
-		.map(|log_param| format!("{} {}", log_param.name, log_param.value))
-		.collect::<Vec<String>>()
-		.join("\n");
+	Ok(Output::Decoded { signature: None, params: decoded_log_params(&event, decoded), typed: false })
+}
 
-	Ok(result)
+fn decode_calldata(path: &str, data: &str) -> anyhow::Result<Output> {
+	let contract = load_contract(path)?;
+	let functions: Vec<&Function> = contract.functions().collect();
+	decode_call(&functions, &decode_hex(data)?)
+}
+
+/// Decodes call data with the function whose selector matches.
+fn decode_call(functions: &[&Function], data: &[u8]) -> anyhow::Result<Output> {
+	if data.len() < 4 {
+		return Err(anyhow!("Call data is shorter than a 4 byte selector"));
+	}
+	let (selector, input) = data.split_at(4);
+
+	let candidates: Vec<&Function> = functions.iter().copied().filter(|f| f.short_signature() == selector).collect();
+	if candidates.is_empty() {
+		return Err(anyhow!("No function with selector 0x{} found", hex::encode(selector)));
+	}
+
+	// Selectors may collide, the first function whose inputs decode wins.
+	let (function, tokens) = candidates
+		.iter()
+		.find_map(|function| function.decode_input(input).ok().map(|tokens| (function, tokens)))
+		.ok_or_else(|| {
+			let signatures = candidates.iter().map(|f| f.canonical_signature()).join(", ");
+			anyhow!("Call data does not match the inputs of {}", signatures)
+		})?;
+
+	Ok(Output::Decoded {
+		signature: Some(function.canonical_signature()),
+		params: decoded_params(&function.inputs, tokens),
+		typed: false,
+	})
+}
+
+fn decode_error(path: &str, data: &str) -> anyhow::Result<Output> {
+	let contract = load_contract(path)?;
+	let data = decode_hex(data)?;
+	if data.len() < 4 {
+		return Err(anyhow!("Revert data is shorter than a 4 byte selector"));
+	}
+	let (selector, input) = data.split_at(4);
+
+	let builtins = builtin_errors();
+	let candidates: Vec<&AbiError> =
+		contract.errors().chain(builtins.iter()).filter(|error| error.signature()[..4] == *selector).collect();
+	if candidates.is_empty() {
+		return Err(anyhow!("No error with selector 0x{} found", hex::encode(selector)));
+	}
+
+	// Selectors may collide, the first error whose inputs decode wins.
+	let (error, tokens) = candidates
+		.iter()
+		.find_map(|error| error.decode(input).ok().map(|tokens| (error, tokens)))
+		.ok_or_else(|| {
+			let signatures = candidates.iter().map(|error| error.canonical_signature()).join(", ");
+			anyhow!("Revert data does not match the inputs of {}", signatures)
+		})?;
+
+	Ok(Output::Decoded {
+		signature: Some(error.canonical_signature()),
+		params: decoded_params(&error.inputs, tokens),
+		typed: false,
+	})
+}
+
+fn decode_logs(paths: &[String], topics: &[String], json: Option<&str>, data: Option<&str>) -> anyhow::Result<Output> {
+	let contracts = load_contracts(paths)?;
+	let events: Vec<&Event> = contracts.iter().flat_map(Contract::events).collect();
+
+	match (json, data) {
+		// Logs which don't match any event are reported in place, so that the others still get decoded.
+		(Some(json), _) => {
+			Ok(Output::List(read_logs(json)?.into_iter().map(|log| decode_any_log(&events, log)).collect()))
+		}
+		(None, Some(data)) => {
+			let topics: Vec<Hash> = topics.iter().map(|t| t.parse()).collect::<Result<_, _>>()?;
+			decode_any_log(&events, RawLog { topics, data: decode_hex(data)? })
+		}
+		(None, None) => Err(anyhow!("Either log data or `--json` is required")),
+	}
+}
+
+/// Decodes a log with the first event whose topic0, or if anonymous whose number of indexed params, matches.
+fn decode_any_log(events: &[&Event], log: RawLog) -> anyhow::Result<Output> {
+	let topic0 = log.topics.first();
+	let by_topic0 = events.iter().filter(|event| !event.anonymous && Some(&event.signature()) == topic0);
+	let by_count = events
+		.iter()
+		.filter(|event| event.anonymous && event.inputs.iter().filter(|p| p.indexed).count() == log.topics.len());
+
+	let (event, decoded) = by_topic0
+		.chain(by_count)
+		.find_map(|event| event.parse_log_validate(log.clone()).ok().map(|decoded| (event, decoded)))
+		.ok_or_else(|| match topic0 {
+			Some(topic0) => anyhow!("No event matches the log with topic0 0x{:x}", topic0),
+			None => anyhow!("No event matches the log without topics"),
+		})?;
+
+	let signature = event.canonical_signature();
+
+	Ok(Output::Decoded { signature: Some(signature), params: decoded_log_params(event, decoded), typed: false })
+}
+
+/// Reads the logs of an `eth_getLogs` result or of a transaction receipt, optionally wrapped in a JSON-RPC response.
+fn read_logs(path: &str) -> anyhow::Result<Vec<RawLog>> {
+	let json: Value = match path {
+		"-" => serde_json::from_reader(std::io::stdin())?,
+		path => serde_json::from_reader(File::open(path)?)?,
+	};
+	let json = json.get("result").unwrap_or(&json);
+	let logs = match json.get("logs").unwrap_or(json) {
+		Value::Array(logs) => logs.iter().collect(),
+		log => vec![log],
+	};
+
+	logs.into_iter().map(read_log).collect()
+}
+
+/// Reads a log from its json form, with `topics` and `data` fields.
+fn read_log(log: &Value) -> anyhow::Result<RawLog> {
+	let topics = match log.get("topics") {
+		Some(Value::Array(topics)) => topics
+			.iter()
+			.map(|topic| Ok(topic.as_str().ok_or_else(|| anyhow!("Log topics must be strings"))?.parse()?))
+			.collect::<anyhow::Result<_>>()?,
+		_ => return Err(anyhow!("Log has no `topics` array")),
+	};
+	let data = log.get("data").and_then(Value::as_str).ok_or_else(|| anyhow!("Log has no `data` string"))?;
+	Ok(RawLog { topics, data: decode_hex(data)? })
+}
+
+/// Decodes newline delimited json requests from `input`, writing a json line with the result or error of each.
+/// The ABIs are loaded once.
+fn batch(paths: &[String], input: &mut dyn BufRead, out: &mut dyn Write) -> anyhow::Result<()> {
+	let contracts = load_contracts(paths)?;
+	let functions: Vec<&Function> = contracts.iter().flat_map(Contract::functions).collect();
+	let events: Vec<&Event> = contracts.iter().flat_map(Contract::events).collect();
+
+	for line in input.lines() {
+		let line = line?;
+		if line.trim().is_empty() {
+			continue;
+		}
+
+		let request = serde_json::from_str::<Value>(&line).map_err(anyhow::Error::from);
+		let id = request.as_ref().ok().and_then(|request| request.get("id")).cloned();
+		let mut result = match request.and_then(|request| batch_request(&contracts, &functions, &events, &request)) {
+			Ok(output) => output.to_json(),
+			Err(err) => json!({ "error": err.to_string() }),
+		};
+		if let Some(id) = id {
+			result["id"] = id;
+		}
+		// Results are streamed, each one as soon as its request is read.
+		writeln!(out, "{result}")?;
+		out.flush()?;
+	}
+	Ok(())
+}
+
+fn batch_request(
+	contracts: &[Contract],
+	functions: &[&Function],
+	events: &[&Event],
+	request: &Value,
+) -> anyhow::Result<Output> {
+	let field = |name: &str| {
+		request.get(name).map(|value| value.as_str().ok_or_else(|| anyhow!("`{name}` must be a string"))).transpose()
+	};
+
+	if let Some(calldata) = field("calldata")? {
+		return decode_call(functions, &decode_hex(calldata)?);
+	}
+	if let Some(output) = field("output")? {
+		let name_or_signature = field("function")?.ok_or_else(|| anyhow!("`output` requires a `function`"))?;
+		// The first ABI with the function wins, otherwise the error of the last one is reported.
+		let mut found = Err(anyhow!("No ABI given"));
+		for contract in contracts {
+			found = find_function(contract, name_or_signature);
+			if found.is_ok() {
+				break;
+			}
+		}
+		return decode_output(found?, &decode_hex(output)?);
+	}
+	if request.get("topics").is_some() {
+		return decode_any_log(events, read_log(request)?);
+	}
+	Err(anyhow!("Expected `calldata`, `output` with `function`, or a log with `topics` and `data`"))
+}
+
+fn decoded_params(params: &[Param], tokens: Vec<Token>) -> Vec<Decoded> {
+	params
+		.iter()
+		.zip(tokens)
+		.map(|(param, token)| Decoded {
+			name: param.name.clone(),
+			kind: param.kind.clone(),
+			components: param.components.clone(),
+			token,
+		})
+		.collect()
+}
+
+fn decoded_log_params(event: &Event, log: Log) -> Vec<Decoded> {
+	event
+		.inputs
+		.iter()
+		.zip(log.params)
+		.map(|(input, param)| Decoded {
+			name: param.name,
+			kind: input.kind.clone(),
+			components: input.components.clone(),
+			token: param.value,
+		})
+		.collect()
+}
+
+/// Decodes hex with an optional `0x` prefix.
+fn decode_hex(data: &str) -> Result<Vec<u8>, hex::FromHexError> {
+	hex::decode(data.strip_prefix("0x").unwrap_or(data))
+}
+
+fn event_filter(path: &str, name_or_signature: &str, topics: &[String], lenient: bool) -> anyhow::Result<Output> {
+	let event = load_event(path, name_or_signature)?;
+	let indexed: Vec<&EventParam> = event.inputs.iter().filter(|param| param.indexed).collect();
+
+	let mut filter: [Topic<Token>; 3] = Default::default();
+	for topic in topics {
+		let (name, values) = topic.split_once('=').ok_or_else(|| anyhow!("Expected `name=value`, got `{}`", topic))?;
+		let position = indexed
+			.iter()
+			.position(|param| param.name == name)
+			.ok_or_else(|| anyhow!("`{}` is not an indexed param of `{}`", name, event.name))?;
+		// Only anonymous events have a fourth indexed param, which topic filters have no place for.
+		if position >= filter.len() {
+			return Err(anyhow!(
+				"`{}` is the fourth indexed param of `{}`, only three can be filtered",
+				name,
+				event.name
+			));
+		}
+		if !filter[position].is_any() {
+			return Err(anyhow!("`{}` is given more than once", name));
+		}
+
+		// Values are read as an array, so that commas in quoted strings, arrays and tuples are fine.
+		let kind = ParamType::Array(Box::new(indexed[position].kind.clone()));
+		let values = match parse_tokens(&[(kind, &format!("[{values}]"))], lenient)?.pop() {
+			Some(Token::Array(values)) => values,
+			_ => unreachable!("arrays are parsed to arrays"),
+		};
+		filter[position] = match values.len() {
+			1 => Topic::This(values.into_iter().next().expect("one value")),
+			_ => Topic::OneOf(values),
+		};
+	}
+
+	let [topic0, topic1, topic2] = filter;
+	let filter = event.filter(RawTopicFilter { topic0, topic1, topic2 })?;
+
+	// Trailing `null`s, which match any topic, can be left out.
+	let mut topics = match serde_json::to_value(filter)? {
+		Value::Array(topics) => topics,
+		_ => unreachable!("topic filters serialize to arrays"),
+	};
+	while topics.last() == Some(&Value::Null) {
+		topics.pop();
+	}
+
+	Ok(Output::Json(Value::Array(topics)))
+}
+
+fn list_signatures(path: &str) -> anyhow::Result<Output> {
+	let contract = load_contract(path)?;
+
+	let functions = contract.functions().map(|function| {
+		vec![
+			("type", Some("function".to_owned())),
+			("signature", Some(function.canonical_signature())),
+			("selector", Some(format!("0x{}", hex::encode(function.short_signature())))),
+		]
+	});
+	let events = contract.events().map(|event| {
+		vec![
+			("type", Some("event".to_owned())),
+			("signature", Some(event.canonical_signature())),
+			// anonymous events have no topic0
+			("topic0", Some(format!("0x{:x}", event.signature())).filter(|_| !event.anonymous)),
+		]
+	});
+	let errors = contract.errors().map(|error| {
+		vec![
+			("type", Some("error".to_owned())),
+			("signature", Some(error.canonical_signature())),
+			("selector", Some(format!("0x{}", hex::encode(&error.signature()[..4])))),
+		]
+	});
+
+	Ok(Output::Table(functions.chain(events).chain(errors).collect()))
+}
+
+/// Normalizes a function, event or error signature as written in Solidity, dropping keywords, param names and
+/// data locations, e.g. `function transfer(address to, uint amount) external` becomes `transfer(address,uint256)`.
+fn normalize_signature(signature: &str) -> anyhow::Result<String> {
+	let invalid = || anyhow!("Invalid signature `{}`", signature);
+	let trimmed = signature.trim();
+	let trimmed = ["function ", "event ", "error "]
+		.iter()
+		.find_map(|keyword| trimmed.strip_prefix(keyword))
+		.unwrap_or(trimmed)
+		.trim_start();
+
+	let open = trimmed.find('(').ok_or_else(invalid)?;
+	let name = trimmed[..open].trim_end();
+	if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$') {
+		return Err(invalid());
+	}
+	// Anything after the params, such as modifiers or returns, is not part of the signature, but has to be
+	// separated from them and have balanced parentheses.
+	let close = matching_paren(trimmed, open).ok_or_else(invalid)?;
+	let rest = &trimmed[close + 1..];
+	if !(rest.is_empty() || rest.starts_with(char::is_whitespace) && balanced(rest)) {
+		return Err(invalid());
+	}
+	let params = normalize_params(&trimmed[open + 1..close]).map_err(|err| anyhow!("{}: {}", invalid(), err))?;
+
+	Ok(format!("{name}({params})"))
+}
+
+/// Returns the position of the parenthesis closing the one at `open`.
+fn matching_paren(s: &str, open: usize) -> Option<usize> {
+	let mut depth = 0;
+	for (i, c) in s[open..].char_indices() {
+		match c {
+			'(' => depth += 1,
+			')' if depth == 1 => return Some(open + i),
+			')' => depth -= 1,
+			_ => (),
+		}
+	}
+	None
+}
+
+/// Returns whether no parenthesis in `s` is closed without being opened and all are closed.
+fn balanced(s: &str) -> bool {
+	let mut depth = 0usize;
+	for c in s.chars() {
+		match c {
+			'(' => depth += 1,
+			')' => match depth.checked_sub(1) {
+				Some(d) => depth = d,
+				None => return false,
+			},
+			_ => (),
+		}
+	}
+	depth == 0
+}
+
+/// Normalizes comma separated params to their canonical types.
+fn normalize_params(params: &str) -> anyhow::Result<String> {
+	if params.trim().is_empty() {
+		return Ok(String::new());
+	}
+
+	let mut types = Vec::new();
+	let mut depth = 0;
+	let mut start = 0;
+	for (i, c) in params.char_indices() {
+		match c {
+			'(' => depth += 1,
+			')' => depth -= 1,
+			',' if depth == 0 => {
+				types.push(normalize_param(&params[start..i])?);
+				start = i + 1;
+			}
+			_ => (),
+		}
+	}
+	types.push(normalize_param(&params[start..])?);
+
+	Ok(types.join(","))
+}
+
+/// Normalizes a param, e.g. `uint[] memory amounts` to `uint256[]`.
+fn normalize_param(param: &str) -> anyhow::Result<String> {
+	let param = param.trim();
+	let param = match param.strip_prefix("tuple") {
+		Some(rest) if rest.trim_start().starts_with('(') => rest.trim_start(),
+		_ => param,
+	};
+
+	let (kind, rest) = match param.starts_with('(') {
+		true => {
+			let close = matching_paren(param, 0).ok_or_else(|| anyhow!("`(` is not closed in `{}`", param))?;
+			let rest = &param[close + 1..];
+			let arrays = rest.split(char::is_whitespace).next().unwrap_or_default();
+			check_arrays(arrays)?;
+			(format!("({}){arrays}", normalize_params(&param[1..close])?), &rest[arrays.len()..])
+		}
+		false => {
+			let kind = param.split_whitespace().next().ok_or_else(|| anyhow!("empty param"))?;
+			let base = &kind[..kind.find('[').unwrap_or(kind.len())];
+			check_elementary(base)?;
+			check_arrays(&kind[base.len()..])?;
+			(kind.to_owned(), &param[kind.len()..])
+		}
+	};
+	// Only keywords such as `indexed` or `memory` and the param name may follow the type.
+	if let Some(word) = rest.split_whitespace().find(|word| !is_identifier(word)) {
+		return Err(anyhow!("unexpected `{}` in `{}`", word, param));
+	}
+
+	Ok(Reader::read(&kind)?.to_string())
+}
+
+/// Checks that `kind` is an elementary type, as `Reader` takes any other name for `uint8`.
+fn check_elementary(kind: &str) -> anyhow::Result<()> {
+	let bits = |prefix: &str| kind.strip_prefix(prefix).map(|size| size.is_empty() || is_size(size, 8, 256, 8));
+	let valid = match kind {
+		"address" | "bool" | "string" | "bytes" => true,
+		_ => {
+			bits("uint")
+				.or_else(|| bits("int"))
+				.or_else(|| kind.strip_prefix("bytes").map(|size| is_size(size, 1, 32, 1)))
+				== Some(true)
+		}
+	};
+	match valid {
+		true => Ok(()),
+		false if is_identifier(kind) && kind.starts_with(char::is_uppercase) => Err(anyhow!(
+			"`{}` is not an elementary type, write contracts and interfaces as `address`, enums as `uint8` and \
+			 structs as tuples",
+			kind
+		)),
+		false => Err(anyhow!("invalid type `{}`", kind)),
+	}
+}
+
+/// Returns whether `size` is a decimal multiple of `step` between `min` and `max`, without leading zeros.
+fn is_size(size: &str, min: usize, max: usize, step: usize) -> bool {
+	!size.starts_with('0') && size.parse::<usize>().is_ok_and(|n| (min..=max).contains(&n) && n % step == 0)
+}
+
+/// Checks array suffixes such as `[][3]`.
+fn check_arrays(arrays: &str) -> anyhow::Result<()> {
+	let mut rest = arrays;
+	while !rest.is_empty() {
+		let close = rest.find(']').filter(|_| rest.starts_with('['));
+		match close {
+			Some(close) if rest[1..close].chars().all(|c| c.is_ascii_digit()) && !rest[1..close].starts_with('0') => {
+				rest = &rest[close + 1..]
+			}
+			_ => return Err(anyhow!("invalid array `{}`", arrays)),
+		}
+	}
+	Ok(())
+}
+
+fn is_identifier(word: &str) -> bool {
+	!word.is_empty() && word.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
 }
 
 fn hash_signature(sig: &str) -> Hash {
@@ -247,7 +965,22 @@ fn hash_signature(sig: &str) -> Hash {
 
 #[cfg(test)]
 mod tests {
-	use super::execute;
+	use super::{batch, run, Format, Opt};
+	use serde_json::Value;
+	use std::io::Cursor;
+	use structopt::StructOpt;
+
+	fn execute<I>(args: I) -> anyhow::Result<String>
+	where
+		I: IntoIterator,
+		I::Item: Into<std::ffi::OsString> + Clone,
+	{
+		let opt = Opt::from_iter(args);
+		let mut out = Vec::new();
+		run(opt.command, opt.output, &mut out)?;
+		let out = String::from_utf8(out)?;
+		Ok(out.strip_suffix('\n').unwrap_or(&out).to_owned())
+	}
 
 	#[test]
 	fn simple_encode() {
@@ -347,6 +1080,49 @@ mod tests {
 		assert_eq!(execute(command).unwrap(), expected);
 	}
 
+	#[test]
+	fn function_encode_from_artifact() {
+		let command = "ethabi encode function ../res/Counter.json number".split(' ');
+		let expected = "8381f58a";
+		assert_eq!(execute(command).unwrap(), expected);
+	}
+
+	#[test]
+	fn constructor_encode() {
+		let command =
+			"ethabi encode constructor ../res/constructor.abi --args-only -p 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+				.split(' ');
+		let expected = "0000000000000000000000005aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+		assert_eq!(execute(command).unwrap(), expected);
+
+		let command =
+			"ethabi encode constructor ../res/constructor.abi -p 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed".split(' ');
+		assert_eq!(
+			execute(command).unwrap_err().to_string(),
+			"No bytecode in ../res/constructor.abi, which may be a plain ABI, unlinked or abstract; give `--bytecode`, \
+			 or `--args-only` to encode the arguments only"
+		);
+
+		let command = "ethabi encode constructor ../res/constructor.abi --bytecode 0x6080 -p 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed".split(' ');
+		let expected = "60800000000000000000000000005aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+		assert_eq!(execute(command).unwrap(), expected);
+
+		let command = "ethabi encode constructor ../res/constructor.abi".split(' ');
+		assert_eq!(execute(command).unwrap_err().to_string(), "Constructor takes 1 params, 0 given");
+	}
+
+	#[test]
+	fn constructor_encode_from_artifact() {
+		let command = "ethabi encode constructor ../res/Counter.json -p 1".split(' ');
+		let expected =
+			"608060405234801561001057600080fd5b500000000000000000000000000000000000000000000000000000000000000001";
+		assert_eq!(execute(command).unwrap(), expected);
+
+		let command = "ethabi encode constructor ../res/Counter.json --args-only -p 1".split(' ');
+		let expected = "0000000000000000000000000000000000000000000000000000000000000001";
+		assert_eq!(execute(command).unwrap(), expected);
+	}
+
 	#[test]
 	fn nonexistent_function() {
 		// This should fail because there is no function called 'nope' in the ABI
@@ -389,15 +1165,34 @@ mod tests {
 	fn int_decode() {
 		let command = "ethabi decode params -t int256 fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe"
 			.split(' ');
-		let expected = "int256 fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe";
+		let expected = "int256 -2";
 		assert_eq!(execute(command).unwrap(), expected);
 	}
 
+	#[test]
+	fn address_decode_checksummed() {
+		let command =
+			"ethabi decode params -t address 0000000000000000000000005aaeb6053f3e94c9b9a09f33669435e7ef1beaed"
+				.split(' ');
+		let expected = "address 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+		assert_eq!(execute(command).unwrap(), expected);
+	}
+
+	#[test]
+	fn address_encode_checksum() {
+		let command = "ethabi encode params -v address 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed".split(' ');
+		let expected = "0000000000000000000000005aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+		assert_eq!(execute(command).unwrap(), expected);
+
+		let command = "ethabi encode params -v address 0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAed".split(' ');
+		assert!(execute(command).is_err());
+	}
+
 	#[test]
 	fn multi_decode() {
 		let command = "ethabi decode params -t bool -t string -t bool 00000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000060000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000096761766f66796f726b0000000000000000000000000000000000000000000000".split(' ');
 		let expected = "bool true
-string gavofyork
+string \"gavofyork\"
 bool false";
 		assert_eq!(execute(command).unwrap(), expected);
 	}
@@ -416,11 +1211,28 @@ bool false";
 		assert_eq!(execute(command).unwrap(), expected);
 	}
 
+	#[test]
+	fn function_decode_input() {
+		let command = "ethabi decode function ../res/eip20.abi transfer --input 0xa9059cbb0000000000000000000000005aaeb6053f3e94c9b9a09f33669435e7ef1beaed00000000000000000000000000000000000000000000000000000000000003e8".split(' ');
+		let expected = "_to 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed
+_value 1000";
+		assert_eq!(execute(command).unwrap(), expected);
+	}
+
+	#[test]
+	fn function_decode_input_selector_mismatch() {
+		let command = "ethabi decode function ../res/eip20.abi approve -i 0xa9059cbb0000000000000000000000005aaeb6053f3e94c9b9a09f33669435e7ef1beaed00000000000000000000000000000000000000000000000000000000000003e8".split(' ');
+		assert_eq!(
+			execute(command).unwrap_err().to_string(),
+			"Call data selector 0xa9059cbb does not match `approve(address,uint256)` (0x095ea7b3)"
+		);
+	}
+
 	#[test]
 	fn log_decode() {
 		let command = "ethabi decode log ../res/event.abi Event -l 0000000000000000000000000000000000000000000000000000000000000001 0000000000000000000000004444444444444444444444444444444444444444".split(' ');
 		let expected = "a true
-b 4444444444444444444444444444444444444444";
+b 0x4444444444444444444444444444444444444444";
 		assert_eq!(execute(command).unwrap(), expected);
 	}
 
@@ -428,10 +1240,330 @@ b 4444444444444444444444444444444444444444";
 	fn log_decode_signature() {
 		let command = "ethabi decode log ../res/event.abi Event(bool,address) -l 0000000000000000000000000000000000000000000000000000000000000001 0000000000000000000000004444444444444444444444444444444444444444".split(' ');
 		let expected = "a true
-b 4444444444444444444444444444444444444444";
+b 0x4444444444444444444444444444444444444444";
+		assert_eq!(execute(command).unwrap(), expected);
+	}
+
+	#[test]
+	fn calldata_decode() {
+		let command = "ethabi decode calldata ../res/eip20.abi 0xa9059cbb0000000000000000000000005aaeb6053f3e94c9b9a09f33669435e7ef1beaed00000000000000000000000000000000000000000000000000000000000003e8".split(' ');
+		let expected = "transfer(address,uint256)
+_to 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed
+_value 1000";
+		assert_eq!(execute(command).unwrap(), expected);
+	}
+
+	#[test]
+	fn calldata_decode_overloaded() {
+		let command = "ethabi decode calldata ../res/test.abi d473a8ed0000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000131000000000000000000000000000000000000000000000000000000000000".split(' ');
+		let expected = "bar(string)
+a \"1\"";
+		assert_eq!(execute(command).unwrap(), expected);
+	}
+
+	#[test]
+	fn calldata_decode_unknown_selector() {
+		let command = "ethabi decode calldata ../res/test.abi 0xdeadbeef".split(' ');
+		assert_eq!(execute(command).unwrap_err().to_string(), "No function with selector 0xdeadbeef found");
+
+		let command = "ethabi decode calldata ../res/test.abi 0xdead".split(' ');
+		assert!(execute(command).is_err());
+	}
+
+	#[test]
+	fn logs_decode_by_topic0() {
+		let command = "ethabi decode logs --abi ../res/eip20.abi -l 0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef -l 0x0000000000000000000000005aaeb6053f3e94c9b9a09f33669435e7ef1beaed -l 0x000000000000000000000000fb6916095ca1df60bb79ce92ce3ea74c37c5d359 0x00000000000000000000000000000000000000000000000000000000000003e8".split(' ');
+		let expected = "Transfer(address,address,uint256)
+from 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed
+to 0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359
+value 1000";
+		assert_eq!(execute(command).unwrap(), expected);
+	}
+
+	#[test]
+	fn logs_decode_anonymous() {
+		let command = "ethabi decode logs -a ../res/eip20.abi -a ../res/event.abi -l 0000000000000000000000000000000000000000000000000000000000000001 0000000000000000000000004444444444444444444444444444444444444444".split(' ');
+		let expected = "Event(bool,address)
+a true
+b 0x4444444444444444444444444444444444444444";
+		assert_eq!(execute(command).unwrap(), expected);
+	}
+
+	#[test]
+	fn logs_decode_json() {
+		let command =
+			"ethabi decode logs -a ../res/eip20.abi -a ../res/event.abi --json ../res/receipt.json".split(' ');
+		let expected = "Transfer(address,address,uint256)
+from 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed
+to 0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359
+value 1000
+
+Event(bool,address)
+a true
+b 0x4444444444444444444444444444444444444444
+
+No event matches the log with topic0 0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925";
+		assert_eq!(execute(command).unwrap(), expected);
+	}
+
+	#[test]
+	fn json_output() {
+		let command = "ethabi encode params -v bool 1 --output json".split(' ');
+		let expected = r#"{"data":"0x0000000000000000000000000000000000000000000000000000000000000001"}"#;
+		assert_eq!(execute(command).unwrap(), expected);
+
+		let command = "ethabi --output json decode params -t (int8,string) 0000000000000000000000000000000000000000000000000000000000000020fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000096761766f66796f726b0000000000000000000000000000000000000000000000".split(' ');
+		let expected = r#"{"params":[{"name":null,"type":"(int8,string)","value":["-2","gavofyork"]}]}"#;
+		assert_eq!(execute(command).unwrap(), expected);
+
+		let command = "ethabi decode function ../res/eip20.abi balanceOf 00000000000000000000000000000000000000000000000000000000000003e8 --output json".split(' ');
+		let expected = r#"{"params":[{"name":"balance","type":"uint256","value":"1000"}]}"#;
+		assert_eq!(execute(command).unwrap(), expected);
+	}
+
+	#[test]
+	fn json_error() {
+		let err = execute("ethabi decode calldata ../res/eip20.abi 0xdeadbeef --output json".split(' ')).unwrap_err();
+		assert_eq!(Format::Json.error(&err), r#"{"error":"No function with selector 0xdeadbeef found"}"#);
+	}
+
+	#[test]
+	fn batch_decode() {
+		let input = r#"{"id": 1, "calldata": "0xa9059cbb0000000000000000000000005aaeb6053f3e94c9b9a09f33669435e7ef1beaed00000000000000000000000000000000000000000000000000000000000003e8"}
+
+{"id": "b", "function": "balanceOf", "output": "0x00000000000000000000000000000000000000000000000000000000000003e8"}
+{"topics": ["0x0000000000000000000000000000000000000000000000000000000000000001"], "data": "0x0000000000000000000000004444444444444444444444444444444444444444"}
+{"calldata": "0xdeadbeef"}
+not json
+"#;
+		let mut out = Vec::new();
+		batch(&["../res/eip20.abi".to_owned(), "../res/event.abi".to_owned()], &mut Cursor::new(input), &mut out)
+			.unwrap();
+
+		let lines: Vec<Value> =
+			String::from_utf8(out).unwrap().lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+		assert_eq!(lines.len(), 5);
+		assert_eq!(lines[0]["id"], 1);
+		assert_eq!(lines[0]["signature"], "transfer(address,uint256)");
+		assert_eq!(lines[0]["params"][1]["value"], "1000");
+		assert_eq!(lines[1]["id"], "b");
+		assert_eq!(lines[1]["params"][0]["name"], "balance");
+		assert_eq!(lines[2]["signature"], "Event(bool,address)");
+		assert_eq!(lines[3]["error"], "No function with selector 0xdeadbeef found");
+		assert!(lines[4]["error"].is_string());
+	}
+
+	#[test]
+	fn batch_flushes_each_line() {
+		/// Records what has been written at each flush.
+		#[derive(Default)]
+		struct Flushes {
+			written: Vec<u8>,
+			flushed: Vec<String>,
+		}
+
+		impl std::io::Write for Flushes {
+			fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+				self.written.extend_from_slice(buf);
+				Ok(buf.len())
+			}
+
+			fn flush(&mut self) -> std::io::Result<()> {
+				self.flushed.push(String::from_utf8(self.written.clone()).unwrap());
+				Ok(())
+			}
+		}
+
+		let input = "{\"calldata\": \"0xdeadbeef\"}\nnot json\n";
+		let mut out = Flushes::default();
+		batch(&["../res/eip20.abi".to_owned()], &mut Cursor::new(input), &mut out).unwrap();
+
+		assert_eq!(out.flushed.len(), 2);
+		assert_eq!(out.flushed[0].lines().count(), 1);
+		assert_eq!(out.flushed[1].lines().count(), 2);
+	}
+
+	#[test]
+	fn error_encode() {
+		let command = "ethabi encode error ../res/errors.abi InsufficientBalance -p 1 -p 2".split(' ');
+		let expected = "cf4791810000000000000000000000000000000000000000000000000000000000000001\
+		                0000000000000000000000000000000000000000000000000000000000000002";
+		assert_eq!(execute(command).unwrap(), expected);
+
+		let command = "ethabi encode error ../res/errors.abi Panic(uint256) -p 0x11".split(' ');
+		let expected = "4e487b710000000000000000000000000000000000000000000000000000000000000011";
+		assert_eq!(execute(command).unwrap(), expected);
+
+		let command = "ethabi encode error ../res/errors.abi Nope".split(' ');
+		assert_eq!(execute(command).unwrap_err().to_string(), "No error named `Nope` found");
+	}
+
+	#[test]
+	fn error_decode() {
+		let command = "ethabi decode error ../res/errors.abi 0xcf4791810000000000000000000000000000000000000000000000000000000000000001\
+		               0000000000000000000000000000000000000000000000000000000000000002"
+			.split(' ');
+		let expected = "InsufficientBalance(uint256,uint256)
+available 1
+required 2";
+		assert_eq!(execute(command).unwrap(), expected);
+
+		let command = "ethabi decode error ../res/errors.abi 0x08c379a0\
+		               0000000000000000000000000000000000000000000000000000000000000020\
+		               0000000000000000000000000000000000000000000000000000000000000004\
+		               626f6f6d00000000000000000000000000000000000000000000000000000000"
+			.split(' ');
+		let expected = "Error(string)
+message \"boom\"";
+		assert_eq!(execute(command).unwrap(), expected);
+
+		let command = "ethabi decode error ../res/errors.abi 4e487b710000000000000000000000000000000000000000000000000000000000000011".split(' ');
+		let expected = "Panic(uint256)
+code 17";
+		assert_eq!(execute(command).unwrap(), expected);
+
+		let command = "ethabi decode error ../res/errors.abi 0xdeadbeef".split(' ');
+		assert_eq!(execute(command).unwrap_err().to_string(), "No error with selector 0xdeadbeef found");
+	}
+
+	#[test]
+	fn sig_selector_and_topic() {
+		let command =
+			["ethabi", "sig", "selector", "function transfer(address to, uint amount) external returns (bool)"];
+		assert_eq!(execute(command).unwrap(), "0xa9059cbb");
+
+		let command =
+			["ethabi", "sig", "topic", "event Transfer(address indexed from, address indexed to, uint value)"];
+		assert_eq!(execute(command).unwrap(), "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+		let command = ["ethabi", "sig", "selector", "--output", "json", "transfer(address,uint256)"];
+		assert_eq!(execute(command).unwrap(), r#"{"selector":"0xa9059cbb"}"#);
+	}
+
+	#[test]
+	fn sig_normalize() {
+		let normalize = |signature| execute(["ethabi", "sig", "normalize", signature]);
+		assert_eq!(normalize("transfer(address, uint)").unwrap(), "transfer(address,uint256)");
+		assert_eq!(normalize("f()").unwrap(), "f()");
+		assert_eq!(
+			normalize("f(tuple(address payable to, int[2] calldata x)[] memory y, bytes b)").unwrap(),
+			"f((address,int256[2])[],bytes)"
+		);
+		assert_eq!(normalize("f((uint,(bool,string))[3] z)").unwrap(), "f((uint256,(bool,string))[3])");
+		assert!(normalize("f(uint,)").is_err());
+		assert!(normalize("f(uint").is_err());
+		assert!(normalize("(uint)").is_err());
+		assert!(normalize("f(intx)").is_err());
+		assert_eq!(normalize("f(uint) external returns (bool)").unwrap(), "f(uint256)");
+		assert_eq!(normalize("f(bytes32[][2] memory x, int8 indexed y)").unwrap(), "f(bytes32[][2],int8)");
+
+		let error = |signature| normalize(signature).unwrap_err().to_string();
+		assert_eq!(
+			error("foo(IERC20 token)"),
+			"Invalid signature `foo(IERC20 token)`: `IERC20` is not an elementary type, write contracts and \
+			 interfaces as `address`, enums as `uint8` and structs as tuples"
+		);
+		assert_eq!(error("f(uint7)"), "Invalid signature `f(uint7)`: invalid type `uint7`");
+		assert_eq!(error("f(bytes33)"), "Invalid signature `f(bytes33)`: invalid type `bytes33`");
+		assert_eq!(error("f(uint[0])"), "Invalid signature `f(uint[0])`: invalid array `[0]`");
+		assert_eq!(error("f((uint)[x])"), "Invalid signature `f((uint)[x])`: invalid array `[x]`");
+		assert_eq!(error("f(uint a, b)"), "Invalid signature `f(uint a, b)`: invalid type `b`");
+		assert_eq!(error("f(uint a.b)"), "Invalid signature `f(uint a.b)`: unexpected `a.b` in `uint a.b`");
+		assert_eq!(error("foo(uint256))"), "Invalid signature `foo(uint256))`");
+		assert_eq!(error("foo(uint256)x"), "Invalid signature `foo(uint256)x`");
+
+		let command = ["ethabi", "sig", "selector", "foo(IERC20 token)"];
+		assert!(execute(command).is_err());
+	}
+
+	#[test]
+	fn sig_list() {
+		let command = "ethabi sig list ../res/errors.abi".split(' ');
+		let expected = "function withdraw(uint256)                    0x2e1a7d4d
+error    InsufficientBalance(uint256,uint256) 0xcf479181
+error    Unauthorized()                       0x82b42900";
+		assert_eq!(execute(command).unwrap(), expected);
+
+		let command = "ethabi sig list ../res/event.abi --output json".split(' ');
+		let expected = r#"[{"signature":"Event(bool,address)","topic0":null,"type":"event"}]"#;
+		assert_eq!(execute(command).unwrap(), expected);
+	}
+
+	#[test]
+	fn abi_show() {
+		let command = "ethabi abi show ../res/vault.abi".split(' ');
+		let expected = "constructor(address owner)
+
+function balanceOf(address account) view returns (uint256) // 0x70a08231
+function withdraw(uint256 amount) // 0x2e1a7d4d
+
+event Withdrawn(address indexed to, uint256 amount) // 0x7084f5476618d8e60b11ef0d7d3f06914655adb8793e28ff7f018d4c76d505d5
+
+error Unauthorized() // 0x82b42900
+
+receive() external payable
+fallback()";
+		assert_eq!(execute(command).unwrap(), expected);
+	}
+
+	#[test]
+	fn abi_show_json() {
+		let command = "ethabi abi show ../res/vault.abi --output json".split(' ');
+		let json: Value = serde_json::from_str(&execute(command).unwrap()).unwrap();
+		assert_eq!(json["constructor"], serde_json::json!({ "inputs": [{ "name": "owner", "type": "address" }] }));
+		assert_eq!(
+			json["functions"][0],
+			serde_json::json!({
+				"name": "balanceOf",
+				"signature": "balanceOf(address)",
+				"selector": "0x70a08231",
+				"stateMutability": "view",
+				"inputs": [{ "name": "account", "type": "address" }],
+				"outputs": [{ "name": null, "type": "uint256" }],
+			})
+		);
+		assert_eq!(
+			json["events"][0]["inputs"],
+			serde_json::json!([
+				{ "name": "to", "type": "address", "indexed": true },
+				{ "name": "amount", "type": "uint256", "indexed": false },
+			])
+		);
+		assert_eq!(json["errors"][0]["selector"], "0x82b42900");
+		assert_eq!((json["receive"].clone(), json["fallback"].clone()), (Value::Bool(true), Value::Bool(true)));
+	}
+
+	#[test]
+	fn filter_topics() {
+		let command = "ethabi filter ../res/eip20.abi Transfer --topic to=0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed,0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359".split(' ');
+		let expected = r#"["0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",null,["0x0000000000000000000000005aaeb6053f3e94c9b9a09f33669435e7ef1beaed","0x000000000000000000000000fb6916095ca1df60bb79ce92ce3ea74c37c5d359"]]"#;
+		assert_eq!(execute(command).unwrap(), expected);
+
+		let command = "ethabi filter ../res/eip20.abi Transfer".split(' ');
+		let expected = r#"["0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"]"#;
 		assert_eq!(execute(command).unwrap(), expected);
 	}
 
+	#[test]
+	fn filter_topics_hashes_dynamic_values() {
+		let command = ["ethabi", "filter", "../res/filter.abi", "Registered", "-t", "name=hello", "-t", "id=1"];
+		let expected = r#"["0x0354b83c7e5660789a9fbab76d1d4dce18d54215dcac7eb9156477f4d90f49c0","0x1c8aff950685c2ed4bc3174f3472287b56d9517b9c948127319a09a7a36deac8","0x0000000000000000000000000000000000000000000000000000000000000001"]"#;
+		assert_eq!(execute(command).unwrap(), expected);
+
+		let command = ["ethabi", "filter", "../res/filter.abi", "Moved", "-t", "a=1", "-t", "c=3"];
+		let expected = r#"["0x0000000000000000000000000000000000000000000000000000000000000001",null,"0x0000000000000000000000000000000000000000000000000000000000000003"]"#;
+		assert_eq!(execute(command).unwrap(), expected);
+
+		let command = ["ethabi", "filter", "../res/filter.abi", "Moved", "-t", "d=4"];
+		assert_eq!(
+			execute(command).unwrap_err().to_string(),
+			"`d` is the fourth indexed param of `Moved`, only three can be filtered"
+		);
+
+		let command = ["ethabi", "filter", "../res/filter.abi", "Registered", "-t", "owner=0x00"];
+		assert_eq!(execute(command).unwrap_err().to_string(), "`owner` is not an indexed param of `Registered`");
+	}
+
 	#[test]
 	fn nonexistent_event() {
 		// This should return an error because no event 'Nope(bool,address)' exists
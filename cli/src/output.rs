@@ -0,0 +1,310 @@
+use std::{fmt, str::FromStr};
+
+use ethabi::{
+	param_type::ParamType, token::to_json, Contract, EventParam, NamedToken, Param, StateMutability, Token, TupleParam,
+};
+use itertools::Itertools;
+use serde_json::{json, Value};
+
+/// Format of the command output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+	/// Lines of text.
+	Text,
+	/// A json document.
+	Json,
+}
+
+impl FromStr for Format {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"text" => Ok(Format::Text),
+			"json" => Ok(Format::Json),
+			_ => Err(format!("unknown output format `{s}`, expected `text` or `json`")),
+		}
+	}
+}
+
+impl Format {
+	/// Renders an error, the json form is `{"error": "..."}`.
+	pub fn error(self, err: &anyhow::Error) -> String {
+		match self {
+			Format::Text => format!("Error: {err:?}"),
+			Format::Json => json!({ "error": err.to_string() }).to_string(),
+		}
+	}
+}
+
+/// Result of a command.
+#[derive(Debug)]
+pub enum Output {
+	/// Encoded data.
+	Encoded(Vec<u8>),
+	/// Decoded params, of the function or event with given signature if any.
+	Decoded {
+		signature: Option<String>,
+		params: Vec<Decoded>,
+		/// Text shows the types of params rather than their names.
+		typed: bool,
+	},
+	/// Outputs for several inputs, failures are reported in place.
+	List(Vec<anyhow::Result<Output>>),
+	/// A json value, shown as json in text too.
+	Json(Value),
+	/// A single named value, text shows just the value.
+	Field(&'static str, String),
+	/// Rows of named values, text shows them as aligned columns with `-` for missing values.
+	Table(Vec<Vec<(&'static str, Option<String>)>>),
+	/// Contents of an ABI, text shows Solidity like declarations with selectors and topics as comments.
+	Abi(Contract),
+}
+
+/// A decoded param.
+#[derive(Debug)]
+pub struct Decoded {
+	/// Param name, may be empty.
+	pub name: String,
+	pub kind: ParamType,
+	/// Components of the tuple the param is or contains.
+	pub components: Vec<TupleParam>,
+	pub token: Token,
+}
+
+impl Decoded {
+	/// A param without name or components, as in `decode params`.
+	pub fn unnamed(kind: ParamType, token: Token) -> Self {
+		Decoded { name: String::new(), kind, components: Vec::new(), token }
+	}
+
+	fn to_json(&self) -> Value {
+		let value = match self.components.is_empty() {
+			true => to_json(&self.token),
+			false => serde_json::to_value(NamedToken::new(self.token.clone(), &self.components))
+				.expect("named tokens serialize to json"),
+		};
+		let name = match self.name.is_empty() {
+			true => Value::Null,
+			false => Value::String(self.name.clone()),
+		};
+		json!({ "name": name, "type": self.kind.to_string(), "value": value })
+	}
+}
+
+impl Output {
+	/// Renders the output in given format.
+	pub fn render(&self, format: Format) -> String {
+		match format {
+			Format::Text => self.to_string(),
+			Format::Json => self.to_json().to_string(),
+		}
+	}
+
+	/// Returns the json form of the output.
+	pub fn to_json(&self) -> Value {
+		match self {
+			Output::Encoded(data) => json!({ "data": format!("0x{}", hex::encode(data)) }),
+			Output::Decoded { signature, params, .. } => {
+				let params: Vec<_> = params.iter().map(Decoded::to_json).collect();
+				match signature {
+					Some(signature) => json!({ "signature": signature, "params": params }),
+					None => json!({ "params": params }),
+				}
+			}
+			Output::List(outputs) => Value::Array(
+				outputs
+					.iter()
+					.map(|output| match output {
+						Ok(output) => output.to_json(),
+						Err(err) => json!({ "error": err.to_string() }),
+					})
+					.collect(),
+			),
+			Output::Json(value) => value.clone(),
+			Output::Field(name, value) => json!({ *name: value }),
+			Output::Table(rows) => Value::Array(
+				rows.iter()
+					.map(|row| row.iter().map(|(name, value)| (name.to_string(), json!(value))).collect())
+					.collect(),
+			),
+			Output::Abi(contract) => abi_to_json(contract),
+		}
+	}
+}
+
+fn abi_to_json(contract: &Contract) -> Value {
+	let params = |params: &[Param]| -> Vec<Value> {
+		params
+			.iter()
+			.map(|param| json!({ "name": optional_name(&param.name), "type": param.kind.to_string() }))
+			.collect()
+	};
+	let functions: Vec<_> = contract
+		.functions()
+		.map(|function| {
+			json!({
+				"name": function.name,
+				"signature": function.canonical_signature(),
+				"selector": format!("0x{}", hex::encode(function.short_signature())),
+				"stateMutability": function.state_mutability,
+				"inputs": params(&function.inputs),
+				"outputs": params(&function.outputs),
+			})
+		})
+		.collect();
+	let events: Vec<_> = contract
+		.events()
+		.map(|event| {
+			let inputs: Vec<_> = event
+				.inputs
+				.iter()
+				.map(|param| {
+					let (name, kind) = (optional_name(&param.name), param.kind.to_string());
+					json!({ "name": name, "type": kind, "indexed": param.indexed })
+				})
+				.collect();
+			json!({
+				"name": event.name,
+				"signature": event.canonical_signature(),
+				// anonymous events have no topic0
+				"topic0": Some(format!("0x{:x}", event.signature())).filter(|_| !event.anonymous),
+				"anonymous": event.anonymous,
+				"inputs": inputs,
+			})
+		})
+		.collect();
+	let errors: Vec<_> = contract
+		.errors()
+		.map(|error| {
+			json!({
+				"name": error.name,
+				"signature": error.canonical_signature(),
+				"selector": format!("0x{}", hex::encode(&error.signature()[..4])),
+				"inputs": params(&error.inputs),
+			})
+		})
+		.collect();
+
+	json!({
+		"constructor": contract.constructor.as_ref().map(|constructor| json!({ "inputs": params(&constructor.inputs) })),
+		"functions": functions,
+		"events": events,
+		"errors": errors,
+		"receive": contract.receive,
+		"fallback": contract.fallback,
+	})
+}
+
+fn optional_name(name: &str) -> Value {
+	match name.is_empty() {
+		true => Value::Null,
+		false => Value::String(name.to_owned()),
+	}
+}
+
+/// Writes the declarations of an ABI, a group of lines for each kind of item.
+fn write_abi(f: &mut fmt::Formatter, contract: &Contract) -> fmt::Result {
+	let params = |params: &[Param]| params.iter().map(|param| declaration(&param.kind, false, &param.name)).join(", ");
+	let event_params = |params: &[EventParam]| {
+		params.iter().map(|param| declaration(&param.kind, param.indexed, &param.name)).join(", ")
+	};
+
+	let constructor =
+		contract.constructor.iter().map(|constructor| format!("constructor({})", params(&constructor.inputs)));
+	let functions = contract.functions().map(|function| {
+		let mutability = match function.state_mutability {
+			StateMutability::Pure => " pure",
+			StateMutability::View => " view",
+			StateMutability::NonPayable => "",
+			StateMutability::Payable => " payable",
+		};
+		let returns = match function.outputs.is_empty() {
+			true => String::new(),
+			false => format!(" returns ({})", params(&function.outputs)),
+		};
+		format!(
+			"function {}({}){mutability}{returns} // 0x{}",
+			function.name,
+			params(&function.inputs),
+			hex::encode(function.short_signature())
+		)
+	});
+	let events = contract.events().map(|event| match event.anonymous {
+		true => format!("event {}({}) anonymous", event.name, event_params(&event.inputs)),
+		false => format!("event {}({}) // 0x{:x}", event.name, event_params(&event.inputs), event.signature()),
+	});
+	let errors = contract.errors().map(|error| {
+		format!("error {}({}) // 0x{}", error.name, params(&error.inputs), hex::encode(&error.signature()[..4]))
+	});
+	// receive functions are always payable, whereas `Contract` doesn't keep whether the fallback is, so it is shown
+	// without modifiers
+	let special = [
+		Some("receive() external payable").filter(|_| contract.receive),
+		Some("fallback()").filter(|_| contract.fallback),
+	];
+
+	let groups = [
+		constructor.collect::<Vec<_>>(),
+		functions.collect(),
+		events.collect(),
+		errors.collect(),
+		special.iter().flatten().map(|line| line.to_string()).collect(),
+	];
+	write!(f, "{}", groups.iter().filter(|lines| !lines.is_empty()).map(|lines| lines.join("\n")).format("\n\n"))
+}
+
+/// Declaration of a param as in Solidity, e.g. `address indexed from`.
+fn declaration(kind: &ParamType, indexed: bool, name: &str) -> String {
+	[kind.to_string().as_str(), if indexed { "indexed" } else { "" }, name].iter().filter(|s| !s.is_empty()).join(" ")
+}
+
+impl fmt::Display for Output {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Output::Encoded(data) => f.write_str(&hex::encode(data)),
+			Output::Decoded { signature, params, typed } => {
+				let params = params.iter().enumerate().map(|(i, param)| {
+					let label = match (typed, param.name.is_empty()) {
+						(true, _) => param.kind.to_string(),
+						(false, true) => i.to_string(),
+						(false, false) => param.name.clone(),
+					};
+					format!("{label} {}", param.token.human_readable())
+				});
+				write!(f, "{}", signature.iter().cloned().chain(params).join("\n"))
+			}
+			Output::List(outputs) => {
+				let outputs = outputs.iter().map(|output| match output {
+					Ok(output) => output.to_string(),
+					Err(err) => err.to_string(),
+				});
+				write!(f, "{}", outputs.format("\n\n"))
+			}
+			Output::Json(value) => write!(f, "{value}"),
+			Output::Field(_, value) => f.write_str(value),
+			Output::Table(rows) => {
+				let cell = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_owned());
+				let mut widths = Vec::new();
+				for row in rows {
+					widths.resize(widths.len().max(row.len()), 0);
+					for (i, (_, value)) in row.iter().enumerate() {
+						widths[i] = widths[i].max(cell(value).len());
+					}
+				}
+				let lines = rows.iter().map(|row| {
+					let last = row.len().saturating_sub(1);
+					row.iter()
+						.enumerate()
+						.map(|(i, (_, value))| match i == last {
+							true => cell(value),
+							false => format!("{:width$}", cell(value), width = widths[i]),
+						})
+						.join(" ")
+				});
+				write!(f, "{}", lines.format("\n"))
+			}
+			Output::Abi(contract) => write_abi(f, contract),
+		}
+	}
+}
@@ -0,0 +1,240 @@
+// Copyright 2015-2019 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::{borrow::Cow, fmt, fs, io, path::Path, path::PathBuf};
+
+use ethabi::{Error, Result};
+use quote::quote;
+
+use crate::{
+	contract::Contract,
+	docs::Docs,
+	load_artifact,
+	options::{Options, Overloads},
+};
+
+/// Generates bindings for a contract into a `.rs` file, e.g. from a build script.
+///
+/// The ABI may also be a Hardhat, Foundry or Truffle artifact, or solc standard json output, in which case
+/// a `deploy` function with the contract bytecode is generated as well.
+///
+/// ```no_run
+/// // build.rs
+/// ethabi_codegen::Abigen::new("eip20", "res/eip20.abi")
+///     .functions(["transfer", "balanceOf"])
+///     .generate()
+///     .unwrap()
+///     .write_to_file(std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("eip20.rs"))
+///     .unwrap();
+/// ```
+///
+/// The file contains a single module, the same one `use_contract!` would generate, and is included with
+/// `include!(concat!(env!("OUT_DIR"), "/eip20.rs"));`.
+#[derive(Debug, Clone)]
+pub struct Abigen {
+	module: String,
+	path: PathBuf,
+	contract: Option<String>,
+	options: Options,
+	visibility: Option<String>,
+	types: Vec<(String, String)>,
+}
+
+impl Abigen {
+	/// Creates a generator of module `module` from the ABI at `path`.
+	pub fn new(module: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+		Abigen {
+			module: module.into(),
+			path: path.into(),
+			contract: None,
+			options: Options::default(),
+			visibility: None,
+			types: vec![],
+		}
+	}
+
+	/// Picks a contract of solc standard json output, addressed as `file:Name` or just `Name`.
+	pub fn contract(mut self, contract: impl Into<String>) -> Self {
+		self.contract = Some(contract.into());
+		self
+	}
+
+	/// Sets how overloaded functions and events are named.
+	pub fn overloads(mut self, overloads: Overloads) -> Self {
+		self.options.overloads = overloads;
+		self
+	}
+
+	/// Names the module of the function or event with given signature, e.g. `foo(uint256)`.
+	pub fn rename(mut self, signature: &str, name: impl Into<String>) -> Self {
+		self.options.renames.insert(signature.replace(' ', ""), name.into());
+		self
+	}
+
+	/// Only generates functions with given names or signatures.
+	pub fn functions<I: IntoIterator<Item = S>, S: AsRef<str>>(mut self, functions: I) -> Self {
+		self.options.functions.only = Some(functions.into_iter().map(|f| f.as_ref().replace(' ', "")).collect());
+		self
+	}
+
+	/// Skips functions with given names or signatures.
+	pub fn skip_functions<I: IntoIterator<Item = S>, S: AsRef<str>>(mut self, functions: I) -> Self {
+		self.options.functions.skip = functions.into_iter().map(|f| f.as_ref().replace(' ', "")).collect();
+		self
+	}
+
+	/// Only generates events with given names or signatures.
+	pub fn events<I: IntoIterator<Item = S>, S: AsRef<str>>(mut self, events: I) -> Self {
+		self.options.events.only = Some(events.into_iter().map(|e| e.as_ref().replace(' ', "")).collect());
+		self
+	}
+
+	/// Skips events with given names or signatures.
+	pub fn skip_events<I: IntoIterator<Item = S>, S: AsRef<str>>(mut self, events: I) -> Self {
+		self.options.events.skip = events.into_iter().map(|e| e.as_ref().replace(' ', "")).collect();
+		self
+	}
+
+	/// Sets the visibility of generated modules, e.g. `pub(crate)`.
+	pub fn visibility(mut self, visibility: impl Into<String>) -> Self {
+		self.visibility = Some(visibility.into());
+		self
+	}
+
+	/// Uses `rust_type`, e.g. `u128`, for the elementary ABI type `abi_type`, e.g. `uint256`.
+	pub fn type_alias(mut self, abi_type: impl Into<String>, rust_type: impl Into<String>) -> Self {
+		self.types.push((abi_type.into(), rust_type.into()));
+		self
+	}
+
+	/// Derives `serde::Serialize` and `serde::Deserialize` for log structs.
+	pub fn serde(mut self, serde: bool) -> Self {
+		self.options.serde = serde;
+		self
+	}
+
+	/// Generates formatted bindings.
+	pub fn generate(&self) -> Result<Bindings> {
+		let mut options = self.options.clone();
+		if let Some(ref visibility) = self.visibility {
+			options.set_visibility(visibility)?;
+		}
+		for (abi_type, rust_type) in &self.types {
+			options.types.insert(abi_type, rust_type)?;
+		}
+
+		let artifact = load_artifact(&self.path, self.contract.as_deref())?;
+		let docs = Docs::contract(artifact.userdoc.as_ref(), artifact.devdoc.as_ref())
+			.item(&format!("Bindings of the contract at `{}`.", self.path.display()));
+		let contract = Contract::new(&artifact.abi, &options)?
+			.with_bytecode(artifact.bytecode)
+			.with_docs(artifact.userdoc.as_ref(), artifact.devdoc.as_ref())
+			.generate();
+
+		let module = syn::parse_str::<syn::Ident>(&self.module)
+			.map_err(|_| Error::Other(Cow::Owned(format!("Invalid module name `{}`", self.module))))?;
+		// unlike macro output, the file is linted as the user's own code
+		let tokens = quote! {
+			#docs
+			#[allow(clippy::all)]
+			#[allow(dead_code)]
+			#[allow(deprecated)]
+			#[allow(unused_imports)]
+			#[allow(unused_mut)]
+			#[allow(unused_variables)]
+			pub mod #module {
+				#contract
+			}
+		};
+		let file: syn::File = syn::parse2(tokens).map_err(|e| Error::Other(Cow::Owned(e.to_string())))?;
+
+		let header = format!(
+			"// This file is generated by ethabi-codegen from `{}`, do not edit it manually.\n\n",
+			self.path.display()
+		);
+		Ok(Bindings(header + &prettyplease::unparse(&file)))
+	}
+}
+
+/// Formatted bindings generated by [`Abigen`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bindings(String);
+
+impl Bindings {
+	/// Writes the bindings to `path`, leaving the file untouched if it is already up to date.
+	pub fn write_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+		let path = path.as_ref();
+		if fs::read_to_string(path).ok().as_ref() == Some(&self.0) {
+			return Ok(());
+		}
+		fs::write(path, &self.0)
+	}
+}
+
+impl fmt::Display for Bindings {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(&self.0)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Abigen;
+
+	#[test]
+	fn generates_formatted_module() {
+		let bindings = Abigen::new("eip20", "../res/eip20.abi")
+			.functions(["transfer"])
+			.skip_events(["Approval"])
+			.visibility("pub(crate)")
+			.generate()
+			.unwrap()
+			.to_string();
+
+		assert!(bindings.starts_with("// This file is generated by ethabi-codegen from `../res/eip20.abi`"));
+		assert!(bindings.contains("pub mod eip20 {\n"));
+		assert!(bindings.contains("    pub(crate) mod functions {\n"));
+		assert!(bindings.contains("pub mod transfer {"));
+		assert!(!bindings.contains("pub mod approve {"));
+		assert!(!bindings.contains("pub struct Approval {"));
+	}
+
+	#[test]
+	fn picks_standard_json_contract() {
+		let bindings =
+			Abigen::new("token", "../res/token.json").contract("token.sol:Token").generate().unwrap().to_string();
+		assert!(bindings.contains("pub mod transfer {"));
+		assert!(bindings.contains("pub const BYTECODE: &[u8]"));
+	}
+
+	#[test]
+	fn documents_bindings() {
+		let bindings = Abigen::new("token", "../res/token.json").generate().unwrap().to_string();
+		assert!(bindings.starts_with(
+			"// This file is generated by ethabi-codegen from `../res/token.json`, do not edit it manually.\n\n\
+			 /// Keeps balances of a fixed supply token.\n///\n/// Token\n"
+		));
+		assert!(bindings.contains("/// Moves tokens to another account.\n"));
+		assert!(bindings.contains("/// Reverts if the sender balance is too low.\n"));
+		assert!(bindings.contains("/// Recipient.\n"));
+		assert!(bindings.contains("/// - `success`: Always true.\n"));
+		assert!(bindings.contains("/// Mints the whole supply to the deployer.\n"));
+		// without NatSpec
+		assert!(bindings.contains("/// Input 0 of type `address`.\n"));
+	}
+
+	#[test]
+	fn reports_invalid_options() {
+		assert!(Abigen::new("eip20", "../res/missing.abi").generate().is_err());
+		assert!(Abigen::new("eip 20", "../res/eip20.abi").generate().is_err());
+		assert!(Abigen::new("eip20", "../res/eip20.abi").visibility("public").generate().is_err());
+		assert!(Abigen::new("eip20", "../res/eip20.abi").type_alias("foo", "u8").generate().is_err());
+		assert!(Abigen::new("eip20", "../res/eip20.abi").functions(["foo"]).generate().is_err());
+		assert!(Abigen::new("token", "../res/token.json").contract("token.sol:Foo").generate().is_err());
+	}
+}
@@ -10,28 +10,43 @@ use proc_macro2::TokenStream;
 use quote::quote;
 
 use super::{
-	from_template_param, get_template_names, input_names, rust_type, template_param_type, to_ethabi_param_vec, to_token,
+	docs::Docs, from_template_param, get_template_names, input_names, options::Options, rust_type, template_param_type,
+	to_ethabi_param_vec, to_token,
 };
 
 /// Structure used to generate contract's constructor interface.
 pub struct Constructor {
+	input_names: Vec<syn::Ident>,
 	inputs_declarations: Vec<TokenStream>,
 	inputs_definitions: Vec<TokenStream>,
 	tokenize: Vec<TokenStream>,
 	recreate_inputs: TokenStream,
+	visibility: TokenStream,
+	/// Input params, for docs.
+	params: Vec<ethabi::Param>,
+	/// NatSpec documentation.
+	docs: Docs,
 }
 
 impl<'a> From<&'a ethabi::Constructor> for Constructor {
 	fn from(c: &'a ethabi::Constructor) -> Self {
+		Constructor::new(c, &Options::default())
+	}
+}
+
+impl Constructor {
+	/// Creates the constructor interface.
+	pub fn new(c: &ethabi::Constructor, options: &Options) -> Self {
+		let types = &options.types;
 		// [param0, hello_world, param2]
 		let input_names = input_names(&c.inputs);
 
 		// [T0: Into<Uint>, T1: Into<Bytes>, T2: IntoIterator<Item = U2>, U2 = Into<Uint>]
 		let inputs_declarations =
-			c.inputs.iter().enumerate().map(|(index, param)| template_param_type(&param.kind, index)).collect();
+			c.inputs.iter().enumerate().map(|(index, param)| template_param_type(&param.kind, index, types)).collect();
 
 		// [Uint, Bytes, Vec<Uint>]
-		let kinds: Vec<_> = c.inputs.iter().map(|param| rust_type(&param.kind)).collect();
+		let kinds: Vec<_> = c.inputs.iter().map(|param| rust_type(&param.kind, types)).collect();
 
 		// [T0, T1, T2]
 		let template_names: Vec<_> = get_template_names(&kinds);
@@ -48,29 +63,47 @@ impl<'a> From<&'a ethabi::Constructor> for Constructor {
 		let tokenize: Vec<_> = input_names
 			.iter()
 			.zip(c.inputs.iter())
-			.map(|(param_name, param)| to_token(&from_template_param(&param.kind, param_name), &param.kind))
+			.map(|(param_name, param)| to_token(&from_template_param(&param.kind, param_name), &param.kind, types))
 			.collect();
 
 		Constructor {
+			input_names,
 			inputs_declarations,
 			inputs_definitions,
 			tokenize,
 			recreate_inputs: to_ethabi_param_vec(&c.inputs),
+			visibility: options.visibility(),
+			params: c.inputs.clone(),
+			docs: Docs::default(),
 		}
 	}
-}
 
-impl Constructor {
+	/// Sets NatSpec documentation of the constructor.
+	pub fn set_docs(&mut self, docs: Docs) {
+		self.docs = docs;
+	}
+
+	/// Generates docs of a function taking constructor params: `summary`, the notice and the documented params.
+	fn docs(&self, summary: &str) -> TokenStream {
+		let summary = match self.docs.notice() {
+			Some(notice) => format!("{summary}\n\n{notice}"),
+			None => summary.to_owned(),
+		};
+		self.docs.with_params(&summary, self.params.iter().map(|param| param.name.as_str()))
+	}
+
 	/// Generates contract constructor interface.
 	pub fn generate(&self) -> TokenStream {
 		let declarations = &self.inputs_declarations;
 		let definitions = &self.inputs_definitions;
 		let tokenize = &self.tokenize;
 		let recreate_inputs = &self.recreate_inputs;
+		let visibility = &self.visibility;
+		let docs = self.docs("Encodes a call to contract's constructor.");
 
 		quote! {
-			/// Encodes a call to contract's constructor.
-			pub fn constructor<#(#declarations),*>(#(#definitions),*) -> ethabi::Bytes {
+			#docs
+			#visibility fn constructor<#(#declarations),*>(#(#definitions),*) -> ethabi::Bytes {
 				let c = ethabi::Constructor {
 					inputs: #recreate_inputs,
 				};
@@ -79,6 +112,23 @@ impl Constructor {
 			}
 		}
 	}
+
+	/// Generates contract deployment with its bytecode in `BYTECODE`.
+	pub fn generate_deploy(&self) -> TokenStream {
+		let declarations = &self.inputs_declarations;
+		// skip `code`
+		let definitions = &self.inputs_definitions[1..];
+		let input_names = &self.input_names;
+		let visibility = &self.visibility;
+		let docs = self.docs("Encodes contract deployment: its bytecode followed by constructor arguments.");
+
+		quote! {
+			#docs
+			#visibility fn deploy<#(#declarations),*>(#(#definitions),*) -> ethabi::Bytes {
+				constructor(BYTECODE.to_vec(), #(#input_names),*)
+			}
+		}
+	}
 }
 
 #[cfg(test)]
@@ -93,7 +143,7 @@ mod tests {
 		let c = Constructor::from(&ethabi_constructor);
 
 		let expected = quote! {
-			/// Encodes a call to contract's constructor.
+			#[doc = " Encodes a call to contract's constructor."]
 			pub fn constructor<>(code: ethabi::Bytes) -> ethabi::Bytes {
 				let c = ethabi::Constructor {
 					inputs: vec![],
@@ -109,19 +159,25 @@ mod tests {
 	#[test]
 	fn test_one_param() {
 		let ethabi_constructor = ethabi::Constructor {
-			inputs: vec![ethabi::Param { name: "foo".into(), kind: ethabi::ParamType::Uint(256), internal_type: None }],
+			inputs: vec![ethabi::Param {
+				name: "foo".into(),
+				kind: ethabi::ParamType::Uint(256),
+				internal_type: None,
+				components: vec![],
+			}],
 		};
 
 		let c = Constructor::from(&ethabi_constructor);
 
 		let expected = quote! {
-			/// Encodes a call to contract's constructor.
+			#[doc = " Encodes a call to contract's constructor."]
 			pub fn constructor<T0: Into<ethabi::Uint> >(code: ethabi::Bytes, foo: T0) -> ethabi::Bytes {
 				let c = ethabi::Constructor {
 					inputs: vec![ethabi::Param {
 						name: "foo".to_owned(),
 						kind: ethabi::ParamType::Uint(256usize),
-						internal_type: None
+						internal_type: None,
+						components: vec![]
 					}],
 				};
 				let tokens = vec![ethabi::Token::Uint(foo.into())];
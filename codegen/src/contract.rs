@@ -0,0 +1,388 @@
+// Copyright 2015-2019 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::{borrow::Cow, collections::BTreeMap};
+
+use ethabi::{
+	natspec::{DevDoc, UserDoc},
+	Error, ParamType, Result,
+};
+use heck::ToSnakeCase;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+	constructor::Constructor,
+	docs::Docs,
+	event::Event,
+	function::Function,
+	options::{Options, Overloads},
+	signature_of,
+};
+
+/// Structure used to generate rust interface for solidity contract.
+pub struct Contract {
+	constructor: Option<Constructor>,
+	functions: Vec<Function>,
+	events: Vec<Event>,
+	bytecode: Option<ethabi::Bytes>,
+	visibility: TokenStream,
+}
+
+impl<'a> From<&'a ethabi::Contract> for Contract {
+	fn from(c: &'a ethabi::Contract) -> Self {
+		Contract {
+			constructor: c.constructor.as_ref().map(Into::into),
+			functions: c.functions().map(Into::into).collect(),
+			events: c.events().map(Into::into).collect(),
+			bytecode: None,
+			visibility: quote! { pub },
+		}
+	}
+}
+
+impl Contract {
+	/// Creates the contract interface, selecting and naming functions and events according to `options`.
+	pub fn new(c: &ethabi::Contract, options: &Options) -> Result<Self> {
+		let functions: Vec<_> =
+			c.functions().map(|f| (f, f.inputs.iter().map(|p| p.kind.clone()).collect::<Vec<_>>())).collect();
+		let events: Vec<_> =
+			c.events().map(|e| (e, e.inputs.iter().map(|p| p.kind.clone()).collect::<Vec<_>>())).collect();
+
+		let known = |name: &str| {
+			functions.iter().any(|(f, params)| f.name == name || signature_of(&f.name, params) == name)
+				|| events.iter().any(|(e, params)| e.name == name || signature_of(&e.name, params) == name)
+		};
+		if let Some(unknown) = options.renames.keys().chain(options.selected_names()).find(|name| !known(name)) {
+			return Err(Error::Other(Cow::Owned(format!("Unknown function or event `{unknown}`"))));
+		}
+
+		let functions: Vec<_> = functions
+			.into_iter()
+			.filter(|(f, params)| options.functions.contains(&f.name, &signature_of(&f.name, params)))
+			.map(|(f, params)| (f, (f.name.as_str(), params)))
+			.collect();
+		let events: Vec<_> = events
+			.into_iter()
+			.filter(|(e, params)| options.events.contains(&e.name, &signature_of(&e.name, params)))
+			.map(|(e, params)| (e, (e.name.as_str(), params)))
+			.collect();
+
+		for (f, (name, params)) in &functions {
+			check_supported(&signature_of(name, params), f.inputs.iter().chain(&f.outputs).map(|p| &p.kind))?;
+		}
+		for (e, (name, params)) in &events {
+			check_supported(&signature_of(name, params), e.inputs.iter().map(|p| &p.kind))?;
+		}
+		if let Some(ref constructor) = c.constructor {
+			check_supported("constructor", constructor.inputs.iter().map(|p| &p.kind))?;
+		}
+
+		let (functions, function_signatures): (Vec<_>, Vec<_>) = functions.into_iter().unzip();
+		let (events, event_signatures): (Vec<_>, Vec<_>) = events.into_iter().unzip();
+		let function_names = rust_names(&function_signatures, options)?;
+		let event_names = rust_names(&event_signatures, options)?;
+
+		Ok(Contract {
+			constructor: c.constructor.as_ref().map(|c| Constructor::new(c, options)),
+			functions: functions
+				.into_iter()
+				.zip(function_names)
+				.map(|(f, name)| Function::new(f, name, options))
+				.collect(),
+			events: events.into_iter().zip(event_names).map(|(e, name)| Event::new(e, name, options)).collect(),
+			bytecode: None,
+			visibility: options.visibility(),
+		})
+	}
+
+	/// Sets the contract creation bytecode, generating a `deploy` function.
+	pub fn with_bytecode(mut self, bytecode: Option<ethabi::Bytes>) -> Self {
+		self.bytecode = bytecode;
+		self
+	}
+
+	/// Sets NatSpec documentation of the constructor, functions and events.
+	pub fn with_docs(mut self, userdoc: Option<&UserDoc>, devdoc: Option<&DevDoc>) -> Self {
+		if let Some(ref mut constructor) = self.constructor {
+			constructor.set_docs(Docs::method(userdoc, devdoc, "constructor"));
+		}
+		for function in &mut self.functions {
+			function.set_docs(Docs::method(userdoc, devdoc, function.signature()));
+		}
+		for event in &mut self.events {
+			event.set_docs(Docs::event(userdoc, devdoc, event.signature()));
+		}
+		self
+	}
+
+	/// Generates rust interface for a contract.
+	pub fn generate(&self) -> TokenStream {
+		let constructor = self.constructor.as_ref().map(Constructor::generate);
+		let functions: Vec<_> = self.functions.iter().map(Function::generate).collect();
+		let call_variants: Vec<_> = self.functions.iter().map(Function::generate_call_variant).collect();
+		let call_decoders: Vec<_> = self.functions.iter().map(Function::generate_call_decoder).collect();
+		let events: Vec<_> = self.events.iter().map(Event::generate_event).collect();
+		let logs: Vec<_> = self.events.iter().map(Event::generate_log).collect();
+		let visibility = &self.visibility;
+		let deploy = self.bytecode.as_ref().map(|bytecode| {
+			let bytecode = proc_macro2::Literal::byte_string(bytecode);
+			let deploy = match self.constructor {
+				Some(ref constructor) => constructor.generate_deploy(),
+				None => quote! {
+					/// Encodes contract deployment, which is just its bytecode.
+					#visibility fn deploy() -> ethabi::Bytes {
+						BYTECODE.to_vec()
+					}
+				},
+			};
+			quote! {
+				/// Contract creation bytecode.
+				#visibility const BYTECODE: &[u8] = #bytecode;
+
+				#deploy
+			}
+		});
+		quote! {
+			use ethabi;
+			const INTERNAL_ERR: &'static str = "`ethabi_derive` internal error";
+
+			#constructor
+			#deploy
+
+			/// Contract's functions.
+			#visibility mod functions {
+				use super::INTERNAL_ERR;
+				use ethabi;
+				#(#functions)*
+
+				/// Decoded call to one of the contract's functions.
+				#[derive(Debug, Clone, PartialEq, Eq)]
+				pub enum Call {
+					#(#call_variants),*
+				}
+
+				/// Decodes function input, picking the function by its selector.
+				pub fn decode_call(input: &[u8]) -> ethabi::Result<Call> {
+					let selector = input.get(..4).ok_or(ethabi::Error::InvalidData)?;
+					#(#call_decoders)*
+					Err(ethabi::Error::InvalidData)
+				}
+			}
+
+			/// Contract's events.
+			#visibility mod events {
+				use super::INTERNAL_ERR;
+				#(#events)*
+			}
+
+			/// Contract's logs.
+			#visibility mod logs {
+				use super::INTERNAL_ERR;
+				use ethabi;
+				#(#logs)*
+			}
+		}
+	}
+}
+
+/// Picks snake case names for generated items, telling overloads apart.
+///
+/// Items which are not overloaded keep their own name.
+fn rust_names(items: &[(&str, Vec<ParamType>)], options: &Options) -> Result<Vec<String>> {
+	let signatures: Vec<_> = items.iter().map(|(name, params)| signature_of(name, params)).collect();
+
+	let mut overloads = BTreeMap::new();
+	for ((name, _), signature) in items.iter().zip(&signatures) {
+		if !options.renames.contains_key(signature) {
+			*overloads.entry(*name).or_insert(0) += 1;
+		}
+	}
+
+	let names: Vec<_> = items
+		.iter()
+		.zip(&signatures)
+		.map(|((name, params), signature)| {
+			if let Some(rename) = options.renames.get(signature) {
+				return rename.to_snake_case();
+			}
+
+			let overloaded = overloads[name] > 1;
+			let name = name.to_snake_case();
+			if !overloaded {
+				return name;
+			}
+
+			match options.overloads {
+				Overloads::Arity => format!("{}_{}", name, params.len()),
+				Overloads::Signature if params.is_empty() => name,
+				Overloads::Signature => {
+					let suffix: Vec<_> = params.iter().map(type_suffix).collect();
+					format!("{}_{}", name, suffix.join("_"))
+				}
+			}
+		})
+		.collect();
+
+	let mut seen = BTreeMap::new();
+	for (name, signature) in names.iter().zip(&signatures) {
+		if let Some(other) = seen.insert(name, signature) {
+			return Err(Error::Other(Cow::Owned(format!(
+				"`{other}` and `{signature}` would both be generated as `{name}`, \
+				use `overloads = \"signature\"` or `rename` to tell them apart"
+			))));
+		}
+	}
+
+	Ok(names)
+}
+
+/// Fails if a param of the item with given signature is, or contains, a tuple, which generated code doesn't
+/// support yet, see https://github.com/openethereum/ethabi/issues/175.
+fn check_supported<'a>(signature: &str, kinds: impl IntoIterator<Item = &'a ParamType>) -> Result<()> {
+	fn has_tuple(kind: &ParamType) -> bool {
+		match *kind {
+			ParamType::Tuple(_) => true,
+			ParamType::Array(ref inner) | ParamType::FixedArray(ref inner, _) => has_tuple(inner),
+			_ => false,
+		}
+	}
+
+	match kinds.into_iter().any(has_tuple) {
+		true => Err(Error::Other(Cow::Owned(format!(
+			"`{signature}` has tuple params, which are not supported yet, see \
+			 https://github.com/openethereum/ethabi/issues/175; skip it to generate the rest"
+		)))),
+		false => Ok(()),
+	}
+}
+
+/// Turns a param type into a valid identifier fragment, e.g. `uint256[]` into `uint256_array`.
+fn type_suffix(kind: &ParamType) -> String {
+	let kind = kind.to_string().replace("[]", "_array");
+	let parts: Vec<_> = kind.split(|c: char| !c.is_ascii_alphanumeric()).filter(|part| !part.is_empty()).collect();
+	parts.join("_")
+}
+
+#[cfg(test)]
+mod test {
+	use ethabi::ParamType;
+	use quote::quote;
+
+	use super::{rust_names, Contract};
+	use crate::options::{Options, Overloads};
+
+	#[test]
+	fn test_no_body() {
+		let ethabi_contract = ethabi::Contract {
+			constructor: None,
+			functions: Default::default(),
+			events: Default::default(),
+			errors: Default::default(),
+			receive: false,
+			fallback: false,
+		};
+
+		let c = Contract::from(&ethabi_contract);
+
+		let expected = quote! {
+			use ethabi;
+			const INTERNAL_ERR: &'static str = "`ethabi_derive` internal error";
+
+			/// Contract's functions.
+			pub mod functions {
+				use super::INTERNAL_ERR;
+				use ethabi;
+
+				/// Decoded call to one of the contract's functions.
+				#[derive(Debug, Clone, PartialEq, Eq)]
+				pub enum Call {}
+
+				/// Decodes function input, picking the function by its selector.
+				pub fn decode_call(input: &[u8]) -> ethabi::Result<Call> {
+					let selector = input.get(..4).ok_or(ethabi::Error::InvalidData)?;
+					Err(ethabi::Error::InvalidData)
+				}
+			}
+
+			/// Contract's events.
+			pub mod events {
+				use super::INTERNAL_ERR;
+			}
+
+			/// Contract's logs.
+			pub mod logs {
+				use super::INTERNAL_ERR;
+				use ethabi;
+			}
+		};
+
+		assert_eq!(expected.to_string(), c.generate().to_string());
+	}
+
+	#[test]
+	fn test_overload_names() {
+		let items = vec![
+			("Transfer", vec![ParamType::Address, ParamType::Uint(256)]),
+			("Transfer", vec![ParamType::Address, ParamType::Array(Box::new(ParamType::Uint(256)))]),
+			("Approval", vec![]),
+		];
+
+		// both overloads have two params
+		let options = Options::default();
+		assert!(rust_names(&items, &options).is_err());
+
+		let options = Options { overloads: Overloads::Signature, ..Default::default() };
+		assert_eq!(
+			rust_names(&items, &options).unwrap(),
+			vec!["transfer_address_uint256", "transfer_address_uint256_array", "approval"]
+		);
+
+		let mut options = Options::default();
+		options.renames.insert("Transfer(address,uint256[])".into(), "transfer_batch".into());
+		assert_eq!(rust_names(&items, &options).unwrap(), vec!["transfer", "transfer_batch", "approval"]);
+	}
+
+	#[test]
+	fn test_unknown_names() {
+		let abi = r#"[{ "type": "function", "name": "foo", "inputs": [], "outputs": [] }]"#;
+		let ethabi_contract = ethabi::Contract::load(abi.as_bytes()).unwrap();
+
+		let mut options = Options::default();
+		options.functions.skip.insert("foo()".into());
+		assert!(Contract::new(&ethabi_contract, &options).is_ok());
+
+		options.functions.skip.insert("bar".into());
+		assert!(Contract::new(&ethabi_contract, &options).is_err());
+	}
+
+	#[test]
+	fn test_unsupported_tuples() {
+		let abi = r#"[
+			{ "type": "function", "name": "foo", "inputs": [], "outputs": [] },
+			{
+				"type": "function",
+				"name": "bar",
+				"inputs": [{ "name": "a", "type": "tuple[]", "components": [{ "name": "b", "type": "bool" }] }],
+				"outputs": []
+			}
+		]"#;
+		let ethabi_contract = ethabi::Contract::load(abi.as_bytes()).unwrap();
+
+		let mut options = Options::default();
+		let err = Contract::new(&ethabi_contract, &options).err().unwrap();
+		assert_eq!(
+			err.to_string(),
+			"`bar((bool)[])` has tuple params, which are not supported yet, see \
+			 https://github.com/openethereum/ethabi/issues/175; skip it to generate the rest"
+		);
+
+		options.functions.skip.insert("bar".into());
+		assert!(Contract::new(&ethabi_contract, &options).is_ok());
+	}
+}
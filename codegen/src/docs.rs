@@ -0,0 +1,189 @@
+// Copyright 2015-2019 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::collections::BTreeMap;
+
+use ethabi::natspec::{DevDoc, DevDocEntry, UserDoc, UserDocEntry};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// NatSpec documentation of a contract, function, event or constructor.
+#[derive(Debug, Clone, Default)]
+pub struct Docs {
+	/// `@notice` text.
+	notice: Option<String>,
+	/// `@dev` text.
+	details: Option<String>,
+	/// `@param` texts by param name.
+	params: BTreeMap<String, String>,
+	/// `@return` texts by output name, or `_0`, `_1`, ... for unnamed outputs.
+	returns: BTreeMap<String, String>,
+}
+
+impl Docs {
+	/// Contract documentation: its notice, and its title and details as developer documentation.
+	pub fn contract(userdoc: Option<&UserDoc>, devdoc: Option<&DevDoc>) -> Self {
+		let details = devdoc.map(|doc| {
+			let lines: Vec<_> = doc.title.iter().chain(doc.details.iter()).map(String::as_str).collect();
+			lines.join("\n\n")
+		});
+		Docs {
+			notice: userdoc.and_then(|doc| doc.notice.clone()),
+			details: details.filter(|details| !details.is_empty()),
+			..Default::default()
+		}
+	}
+
+	/// Documentation of a function with given signature, e.g. `transfer(address,uint256)`, or of `constructor`.
+	pub fn method(userdoc: Option<&UserDoc>, devdoc: Option<&DevDoc>, signature: &str) -> Self {
+		Docs::entry(
+			userdoc.and_then(|doc| doc.methods.get(signature)),
+			devdoc.and_then(|doc| doc.methods.get(signature)),
+		)
+	}
+
+	/// Documentation of an event with given signature, e.g. `Transfer(address,address,uint256)`.
+	pub fn event(userdoc: Option<&UserDoc>, devdoc: Option<&DevDoc>, signature: &str) -> Self {
+		Docs::entry(userdoc.and_then(|doc| doc.events.get(signature)), devdoc.and_then(|doc| doc.events.get(signature)))
+	}
+
+	fn entry(user: Option<&UserDocEntry>, dev: Option<&DevDocEntry>) -> Self {
+		Docs {
+			notice: user.and_then(|entry| entry.notice.clone()),
+			details: dev.and_then(|entry| entry.details.clone()),
+			params: dev.map(|entry| entry.params.clone()).unwrap_or_default(),
+			returns: dev.map(|entry| entry.returns.clone()).unwrap_or_default(),
+		}
+	}
+
+	/// `@notice` text.
+	pub fn notice(&self) -> Option<&str> {
+		self.notice.as_deref()
+	}
+
+	/// Generates docs of an item: the notice, or `fallback` without one, followed by the details.
+	pub fn item(&self, fallback: &str) -> TokenStream {
+		let notice = self.notice.as_deref().unwrap_or(fallback);
+		match self.details {
+			Some(ref details) => doc_lines(&format!("{notice}\n\n{details}")),
+			None => doc_lines(notice),
+		}
+	}
+
+	/// Generates docs of a param, using `fallback` if it isn't documented.
+	pub fn param(&self, name: &str, fallback: &str) -> TokenStream {
+		doc_lines(self.params.get(name).map(String::as_str).unwrap_or(fallback))
+	}
+
+	/// Generates docs of a function taking params named `params`: `summary` followed by the documented params.
+	pub fn with_params<'a>(&self, summary: &str, params: impl IntoIterator<Item = &'a str>) -> TokenStream {
+		let params =
+			params.into_iter().filter_map(|name| self.params.get(name).map(|doc| format!("- `{name}`: {doc}")));
+		doc_list(summary, "Params", params)
+	}
+
+	/// Generates docs of a function returning `outputs`: `summary` followed by the documented outputs.
+	pub fn with_returns(&self, summary: &str, outputs: &[ethabi::Param]) -> TokenStream {
+		let returns = outputs.iter().enumerate().filter_map(|(index, output)| {
+			let key = if output.name.is_empty() { format!("_{index}") } else { output.name.clone() };
+			self.returns.get(&key).map(|doc| format!("- `{key}`: {doc}"))
+		});
+		doc_list(summary, "Returns", returns)
+	}
+}
+
+/// Generates `summary`, followed by a section with the `items` list if there are any.
+fn doc_list(summary: &str, section: &str, items: impl Iterator<Item = String>) -> TokenStream {
+	let items: Vec<_> = items.collect();
+	if items.is_empty() {
+		return doc_lines(summary);
+	}
+	doc_lines(&format!("{summary}\n\n# {section}\n\n{}", items.join("\n")))
+}
+
+/// Generates a `#[doc]` attribute for each line of `text`.
+fn doc_lines(text: &str) -> TokenStream {
+	let lines =
+		text.lines().map(|line| if line.trim().is_empty() { String::new() } else { format!(" {}", line.trim()) });
+	quote! { #(#[doc = #lines])* }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Docs;
+	use ethabi::natspec::{DevDoc, UserDoc};
+
+	#[test]
+	fn test_method_docs() {
+		let userdoc: UserDoc =
+			serde_json::from_str(r#"{ "methods": { "transfer(address,uint256)": { "notice": "Moves tokens." } } }"#)
+				.unwrap();
+		let devdoc: DevDoc = serde_json::from_str(
+			r#"{
+				"methods": {
+					"transfer(address,uint256)": {
+						"details": "Reverts on low balance.",
+						"params": { "to": "Recipient." },
+						"returns": { "_0": "Always true." }
+					}
+				}
+			}"#,
+		)
+		.unwrap();
+		let docs = Docs::method(Some(&userdoc), Some(&devdoc), "transfer(address,uint256)");
+
+		let to = ethabi::Param {
+			name: "to".into(),
+			kind: ethabi::ParamType::Address,
+			internal_type: None,
+			components: vec![],
+		};
+		let out =
+			ethabi::Param { name: "".into(), kind: ethabi::ParamType::Bool, internal_type: None, components: vec![] };
+
+		assert_eq!(
+			docs.item("Function.").to_string(),
+			quote::quote! {
+				#[doc = " Moves tokens."]
+				#[doc = ""]
+				#[doc = " Reverts on low balance."]
+			}
+			.to_string()
+		);
+		assert_eq!(docs.param("to", "Input.").to_string(), quote::quote! { #[doc = " Recipient."] }.to_string());
+		assert_eq!(docs.param("value", "Input.").to_string(), quote::quote! { #[doc = " Input."] }.to_string());
+		assert_eq!(
+			docs.with_params("Encodes function input.", [to.name.as_str()]).to_string(),
+			quote::quote! {
+				#[doc = " Encodes function input."]
+				#[doc = ""]
+				#[doc = " # Params"]
+				#[doc = ""]
+				#[doc = " - `to`: Recipient."]
+			}
+			.to_string()
+		);
+		assert_eq!(
+			docs.with_returns("Decodes function output.", &[out]).to_string(),
+			quote::quote! {
+				#[doc = " Decodes function output."]
+				#[doc = ""]
+				#[doc = " # Returns"]
+				#[doc = ""]
+				#[doc = " - `_0`: Always true."]
+			}
+			.to_string()
+		);
+	}
+
+	#[test]
+	fn test_missing_docs() {
+		let docs = Docs::method(None, None, "transfer(address,uint256)");
+		assert_eq!(docs.item("Function.").to_string(), quote::quote! { #[doc = " Function."] }.to_string());
+	}
+}
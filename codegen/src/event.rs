@@ -10,11 +10,23 @@ use heck::{ToSnakeCase, ToUpperCamelCase};
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 
-use super::{from_token, get_template_names, rust_type, to_syntax_string, to_token};
+use super::{
+	docs::Docs, from_token, get_template_names, options::Options, rust_type, signature_of, to_syntax_string, to_token,
+};
 
 /// Structure used to generate contract's event interface.
 pub struct Event {
 	name: String,
+	/// Name of the generated module and log struct, in snake case.
+	rust_name: String,
+	/// Event signature, e.g. `Transfer(address,address,uint256)`.
+	signature: String,
+	/// NatSpec documentation.
+	docs: Docs,
+	/// Event params, for docs.
+	params: Vec<ethabi::EventParam>,
+	/// Whether the log struct derives serde traits.
+	serde: bool,
 	log_fields: Vec<TokenStream>,
 	recreate_inputs_quote: TokenStream,
 	log_init: Vec<TokenStream>,
@@ -27,6 +39,14 @@ pub struct Event {
 
 impl<'a> From<&'a ethabi::Event> for Event {
 	fn from(e: &'a ethabi::Event) -> Self {
+		Event::new(e, e.name.to_snake_case(), &Options::default())
+	}
+}
+
+impl Event {
+	/// Creates the event interface, generated into a module and log struct derived from `rust_name`.
+	pub fn new(e: &ethabi::Event, rust_name: String, options: &Options) -> Self {
+		let types = &options.types;
 		let names: Vec<_> = e
 			.inputs
 			.iter()
@@ -43,19 +63,19 @@ impl<'a> From<&'a ethabi::Event> for Event {
 				}
 			})
 			.collect();
-		let kinds: Vec<_> = e.inputs.iter().map(|param| rust_type(&param.kind)).collect();
+		let kinds: Vec<_> = e.inputs.iter().map(|param| rust_type(&param.kind, types)).collect();
 		let log_fields =
 			names.iter().zip(kinds.iter()).map(|(param_name, kind)| quote! { pub #param_name: #kind }).collect();
 
 		let log_iter = quote! { log.next().expect(INTERNAL_ERR).value };
 
-		let to_log: Vec<_> = e.inputs.iter().map(|param| from_token(&param.kind, &log_iter)).collect();
+		let to_log: Vec<_> = e.inputs.iter().map(|param| from_token(&param.kind, &log_iter, types)).collect();
 
 		let log_init =
 			names.iter().zip(to_log.iter()).map(|(param_name, convert)| quote! { #param_name: #convert }).collect();
 
 		let topic_kinds: Vec<_> =
-			e.inputs.iter().filter(|param| param.indexed).map(|param| rust_type(&param.kind)).collect();
+			e.inputs.iter().filter(|param| param.indexed).map(|param| rust_type(&param.kind, types)).collect();
 		let topic_names: Vec<_> = e
 			.inputs
 			.iter()
@@ -96,7 +116,7 @@ impl<'a> From<&'a ethabi::Event> for Event {
 			.map(|(index, (param_name, param))| {
 				let topic = syn::Ident::new(&format!("topic{index}"), Span::call_site());
 				let i = quote! { i };
-				let to_token = to_token(&i, &param.kind);
+				let to_token = to_token(&i, &param.kind, types);
 				quote! { #topic: #param_name.into().map(|#i| #to_token), }
 			})
 			.collect();
@@ -113,7 +133,9 @@ impl<'a> From<&'a ethabi::Event> for Event {
 					ethabi::EventParam {
 						name: #name.to_owned(),
 						kind: #kind,
-						indexed: #indexed
+						indexed: #indexed,
+						internal_type: None,
+						components: vec![]
 					}
 				}
 			})
@@ -122,6 +144,11 @@ impl<'a> From<&'a ethabi::Event> for Event {
 
 		Event {
 			name: e.name.clone(),
+			rust_name,
+			signature: signature_of(&e.name, &e.inputs.iter().map(|p| p.kind.clone()).collect::<Vec<_>>()),
+			docs: Docs::default(),
+			params: e.inputs.clone(),
+			serde: options.serde,
 			log_fields,
 			recreate_inputs_quote,
 			log_init,
@@ -132,18 +159,39 @@ impl<'a> From<&'a ethabi::Event> for Event {
 			filter_init,
 		}
 	}
-}
 
-impl Event {
+	/// Event signature, e.g. `Transfer(address,address,uint256)`.
+	pub fn signature(&self) -> &str {
+		&self.signature
+	}
+
+	/// Sets NatSpec documentation of the event.
+	pub fn set_docs(&mut self, docs: Docs) {
+		self.docs = docs;
+	}
+
 	/// Generates event log struct.
 	pub fn generate_log(&self) -> TokenStream {
-		let name = syn::Ident::new(&self.name.to_upper_camel_case(), Span::call_site());
+		let name = syn::Ident::new(&self.rust_name.to_upper_camel_case(), Span::call_site());
 		let log_fields = &self.log_fields;
+		let field_docs = self.params.iter().enumerate().map(|(index, param)| {
+			let kind = if param.indexed { "Indexed param" } else { "Param" };
+			let fallback = if param.name.is_empty() {
+				format!("{} {} of type `{}`.", kind, index, param.kind)
+			} else {
+				format!("{} `{}` of type `{}`.", kind, param.name, param.kind)
+			};
+			self.docs.param(&param.name, &fallback)
+		});
+		let docs = self.docs.item(&format!("Log of event `{}`.", self.signature));
+		let serde = if self.serde { Some(quote! { #[derive(serde::Serialize, serde::Deserialize)] }) } else { None };
 
 		quote! {
+			#docs
 			#[derive(Debug, Clone, PartialEq, Eq)]
+			#serde
 			pub struct #name {
-				#(#log_fields),*
+				#(#field_docs #log_fields),*
 			}
 		}
 	}
@@ -151,8 +199,8 @@ impl Event {
 	/// Generates rust interface for contract's event.
 	pub fn generate_event(&self) -> TokenStream {
 		let name_as_string = &self.name.to_upper_camel_case();
-		let name = syn::Ident::new(&self.name.to_snake_case(), Span::call_site());
-		let camel_name = syn::Ident::new(&self.name.to_upper_camel_case(), Span::call_site());
+		let name = syn::Ident::new(&self.rust_name.to_snake_case(), Span::call_site());
+		let camel_name = syn::Ident::new(&self.rust_name.to_upper_camel_case(), Span::call_site());
 		let recreate_inputs_quote = &self.recreate_inputs_quote;
 		let anonymous = &self.anonymous;
 		let log_init = &self.log_init;
@@ -160,12 +208,20 @@ impl Event {
 		let filter_declarations = &self.filter_declarations;
 		let filter_definitions = &self.filter_definitions;
 		let wildcard_filter_params = &self.wildcard_filter_params;
+		// `RawTopicFilter` has three topics, all of them may already be set
+		let filter_rest = if filter_init.len() < 3 { Some(quote! { ..Default::default() }) } else { None };
+		let docs = self.docs.item(&format!("Event `{}`.", self.signature));
+		let indexed_names = self.params.iter().filter(|param| param.indexed).map(|param| param.name.as_str());
+		let filter_docs =
+			self.docs.with_params("Creates a filter matching the event, by its indexed params.", indexed_names);
 
 		quote! {
+			#docs
 			pub mod #name {
 				use ethabi;
 				use super::INTERNAL_ERR;
 
+				/// Returns the event definition.
 				pub fn event() -> ethabi::Event {
 					ethabi::Event {
 						name: #name_as_string.into(),
@@ -174,20 +230,23 @@ impl Event {
 					}
 				}
 
+				#filter_docs
 				pub fn filter<#(#filter_declarations),*>(#(#filter_definitions),*) -> ethabi::TopicFilter {
 					let raw = ethabi::RawTopicFilter {
 						#(#filter_init)*
-						..Default::default()
+						#filter_rest
 					};
 
 					let e = event();
 					e.filter(raw).expect(INTERNAL_ERR)
 				}
 
+				/// Creates a filter matching any occurrence of the event.
 				pub fn wildcard_filter() -> ethabi::TopicFilter {
 					filter(#(#wildcard_filter_params),*)
 				}
 
+				/// Parses a log of the event.
 				pub fn parse_log(log: ethabi::RawLog) -> ethabi::Result<super::super::logs::#camel_name> {
 					let e = event();
 					let mut log = e.parse_log(log)?.params.into_iter();
@@ -213,6 +272,7 @@ mod tests {
 		let e = Event::from(&ethabi_event);
 
 		let expected = quote! {
+			#[doc = " Log of event `hello()`."]
 			#[derive(Debug, Clone, PartialEq, Eq)]
 			pub struct Hello {}
 		};
@@ -227,10 +287,12 @@ mod tests {
 		let e = Event::from(&ethabi_event);
 
 		let expected = quote! {
+			#[doc = " Event `hello()`."]
 			pub mod hello {
 				use ethabi;
 				use super::INTERNAL_ERR;
 
+				/// Returns the event definition.
 				pub fn event() -> ethabi::Event {
 					ethabi::Event {
 						name: "Hello".into(),
@@ -239,6 +301,7 @@ mod tests {
 					}
 				}
 
+				#[doc = " Creates a filter matching the event, by its indexed params."]
 				pub fn filter<>() -> ethabi::TopicFilter {
 					let raw = ethabi::RawTopicFilter {
 						..Default::default()
@@ -248,10 +311,12 @@ mod tests {
 					e.filter(raw).expect(INTERNAL_ERR)
 				}
 
+				/// Creates a filter matching any occurrence of the event.
 				pub fn wildcard_filter() -> ethabi::TopicFilter {
 					filter()
 				}
 
+				/// Parses a log of the event.
 				pub fn parse_log(log: ethabi::RawLog) -> ethabi::Result<super::super::logs::Hello> {
 					let e = event();
 					let mut log = e.parse_log(log)?.params.into_iter();
@@ -268,29 +333,40 @@ mod tests {
 	fn test_event_with_one_input() {
 		let ethabi_event = ethabi::Event {
 			name: "one".into(),
-			inputs: vec![ethabi::EventParam { name: "foo".into(), kind: ethabi::ParamType::Address, indexed: true }],
+			inputs: vec![ethabi::EventParam {
+				name: "foo".into(),
+				kind: ethabi::ParamType::Address,
+				indexed: true,
+				internal_type: None,
+				components: vec![],
+			}],
 			anonymous: false,
 		};
 
 		let e = Event::from(&ethabi_event);
 
 		let expected = quote! {
+			#[doc = " Event `one(address)`."]
 			pub mod one {
 				use ethabi;
 				use super::INTERNAL_ERR;
 
+				/// Returns the event definition.
 				pub fn event() -> ethabi::Event {
 					ethabi::Event {
 						name: "One".into(),
 						inputs: vec![ethabi::EventParam {
 							name: "foo".to_owned(),
 							kind: ethabi::ParamType::Address,
-							indexed: true
+							indexed: true,
+							internal_type: None,
+							components: vec![]
 						}],
 						anonymous: false,
 					}
 				}
 
+				#[doc = " Creates a filter matching the event, by its indexed params."]
 				pub fn filter<T0: Into<ethabi::Topic<ethabi::Address>>>(foo: T0) -> ethabi::TopicFilter {
 					let raw = ethabi::RawTopicFilter {
 						topic0: foo.into().map(|i| ethabi::Token::Address(i)),
@@ -301,10 +377,12 @@ mod tests {
 					e.filter(raw).expect(INTERNAL_ERR)
 				}
 
+				/// Creates a filter matching any occurrence of the event.
 				pub fn wildcard_filter() -> ethabi::TopicFilter {
 					filter(ethabi::Topic::Any)
 				}
 
+				/// Parses a log of the event.
 				pub fn parse_log(log: ethabi::RawLog) -> ethabi::Result<super::super::logs::One> {
 					let e = event();
 					let mut log = e.parse_log(log)?.params.into_iter();
@@ -323,15 +401,23 @@ mod tests {
 	fn test_log_with_one_field() {
 		let ethabi_event = ethabi::Event {
 			name: "one".into(),
-			inputs: vec![ethabi::EventParam { name: "foo".into(), kind: ethabi::ParamType::Address, indexed: false }],
+			inputs: vec![ethabi::EventParam {
+				name: "foo".into(),
+				kind: ethabi::ParamType::Address,
+				indexed: false,
+				internal_type: None,
+				components: vec![],
+			}],
 			anonymous: false,
 		};
 
 		let e = Event::from(&ethabi_event);
 
 		let expected = quote! {
+			#[doc = " Log of event `one(address)`."]
 			#[derive(Debug, Clone, PartialEq, Eq)]
 			pub struct One {
+				#[doc = " Param `foo` of type `address`."]
 				pub foo: ethabi::Address
 			}
 		};
@@ -344,13 +430,27 @@ mod tests {
 		let ethabi_event = ethabi::Event {
 			name: "many".into(),
 			inputs: vec![
-				ethabi::EventParam { name: "foo".into(), kind: ethabi::ParamType::Address, indexed: false },
+				ethabi::EventParam {
+					name: "foo".into(),
+					kind: ethabi::ParamType::Address,
+					indexed: false,
+					internal_type: None,
+					components: vec![],
+				},
 				ethabi::EventParam {
 					name: "bar".into(),
 					kind: ethabi::ParamType::Array(Box::new(ethabi::ParamType::String)),
 					indexed: false,
+					internal_type: None,
+					components: vec![],
+				},
+				ethabi::EventParam {
+					name: "xyz".into(),
+					kind: ethabi::ParamType::Uint(256),
+					indexed: false,
+					internal_type: None,
+					components: vec![],
 				},
-				ethabi::EventParam { name: "xyz".into(), kind: ethabi::ParamType::Uint(256), indexed: false },
 			],
 			anonymous: false,
 		};
@@ -358,10 +458,14 @@ mod tests {
 		let e = Event::from(&ethabi_event);
 
 		let expected = quote! {
+			#[doc = " Log of event `many(address,string[],uint256)`."]
 			#[derive(Debug, Clone, PartialEq, Eq)]
 			pub struct Many {
+				#[doc = " Param `foo` of type `address`."]
 				pub foo: ethabi::Address,
+				#[doc = " Param `bar` of type `string[]`."]
 				pub bar: Vec<String>,
+				#[doc = " Param `xyz` of type `uint256`."]
 				pub xyz: ethabi::Uint
 			}
 		};
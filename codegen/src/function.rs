@@ -6,13 +6,13 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use heck::ToSnakeCase;
+use heck::{ToSnakeCase, ToUpperCamelCase};
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 
 use super::{
-	from_template_param, from_token, get_output_kinds, get_template_names, input_names, rust_type, template_param_type,
-	to_ethabi_param_vec, to_token,
+	docs::Docs, from_template_param, from_token, get_output_kinds, get_template_names, input_names, options::Options,
+	rust_type, signature_of, template_param_type, to_ethabi_param_vec, to_token,
 };
 
 struct TemplateParam {
@@ -39,8 +39,22 @@ struct Inputs {
 	tokenize: Vec<TokenStream>,
 	/// Template params.
 	template_params: Vec<TemplateParam>,
+	/// Fields of the decoded inputs struct.
+	///
+	/// ```text
+	/// [pub param0: Uint, pub hello_world: Bytes, pub param2: Vec<Uint>]
+	/// ```
+	fields: Vec<TokenStream>,
+	/// Initializers of the decoded inputs struct fields.
+	///
+	/// ```text
+	/// [param0: tokens.next().expect(INTERNAL_ERR).into_uint().expect(INTERNAL_ERR), ...]
+	/// ```
+	decode: Vec<TokenStream>,
 	/// Quote used to recreate `Vec<ethabi::Param>`
 	recreate_quote: TokenStream,
+	/// Input params, for docs.
+	params: Vec<ethabi::Param>,
 }
 
 struct Outputs {
@@ -50,12 +64,22 @@ struct Outputs {
 	result: TokenStream,
 	/// Quote used to recreate `Vec<ethabi::Param>`.
 	recreate_quote: TokenStream,
+	/// Output params, for docs.
+	params: Vec<ethabi::Param>,
 }
 
 /// Structure used to generate contract's function interface.
 pub struct Function {
 	/// Function name.
 	name: String,
+	/// Name of the generated module.
+	module_name: String,
+	/// Function signature, e.g. `transfer(address,uint256)`.
+	signature: String,
+	/// NatSpec documentation.
+	docs: Docs,
+	/// Function 4 byte selector.
+	selector: [u8; 4],
 	/// Function input params.
 	inputs: Inputs,
 	/// Function output params.
@@ -70,14 +94,24 @@ pub struct Function {
 
 impl<'a> From<&'a ethabi::Function> for Function {
 	fn from(f: &'a ethabi::Function) -> Self {
+		Function::new(f, f.name.to_snake_case(), &Options::default())
+	}
+}
+
+impl Function {
+	/// Creates the function interface, generated into a module called `module_name`.
+	pub fn new(f: &ethabi::Function, module_name: String, options: &Options) -> Self {
+		let types = &options.types;
+
 		// [param0, hello_world, param2]
 		let input_names = input_names(&f.inputs);
 
 		// [T0: Into<Uint>, T1: Into<Bytes>, T2: IntoIterator<Item = U2>, U2 = Into<Uint>]
-		let declarations = f.inputs.iter().enumerate().map(|(index, param)| template_param_type(&param.kind, index));
+		let declarations =
+			f.inputs.iter().enumerate().map(|(index, param)| template_param_type(&param.kind, index, types));
 
 		// [Uint, Bytes, Vec<Uint>]
-		let kinds: Vec<_> = f.inputs.iter().map(|param| rust_type(&param.kind)).collect();
+		let kinds: Vec<_> = f.inputs.iter().map(|param| rust_type(&param.kind, types)).collect();
 
 		// [T0, T1, T2]
 		let template_names: Vec<_> = get_template_names(&kinds);
@@ -97,10 +131,24 @@ impl<'a> From<&'a ethabi::Function> for Function {
 		let tokenize: Vec<_> = input_names
 			.iter()
 			.zip(f.inputs.iter())
-			.map(|(param_name, param)| to_token(&from_template_param(&param.kind, param_name), &param.kind))
+			.map(|(param_name, param)| to_token(&from_template_param(&param.kind, param_name), &param.kind, types))
 			.collect();
 
-		let output_result = get_output_kinds(&f.outputs);
+		// [pub param0: Uint, pub hello_world: Bytes, pub param2: Vec<Uint>]
+		let fields =
+			input_names.iter().zip(kinds.iter()).map(|(param_name, kind)| quote! { pub #param_name: #kind }).collect();
+
+		let token = quote! { tokens.next().expect(INTERNAL_ERR) };
+		let decode = input_names
+			.iter()
+			.zip(f.inputs.iter())
+			.map(|(param_name, param)| {
+				let convert = from_token(&param.kind, &token, types);
+				quote! { #param_name: #convert }
+			})
+			.collect();
+
+		let output_result = get_output_kinds(&f.outputs, types);
 
 		let output_implementation = match f.outputs.len() {
 			0 => quote! {
@@ -109,15 +157,24 @@ impl<'a> From<&'a ethabi::Function> for Function {
 			},
 			1 => {
 				let o = quote! { out };
-				let from_first = from_token(&f.outputs[0].kind, &o);
-				quote! {
-					let out = self.0.decode_output(output)?.into_iter().next().expect(INTERNAL_ERR);
-					Ok(#from_first)
+				let from_first = from_token(&f.outputs[0].kind, &o, types);
+				if types.is_used_by(&f.outputs[0].kind) {
+					// conversion to a custom type already ends with `?`
+					quote! {
+						let out = self.0.decode_output(output)?.into_iter().next().expect(INTERNAL_ERR);
+						let out = #from_first;
+						Ok(out)
+					}
+				} else {
+					quote! {
+						let out = self.0.decode_output(output)?.into_iter().next().expect(INTERNAL_ERR);
+						Ok(#from_first)
+					}
 				}
 			}
 			_ => {
 				let o = quote! { out.next().expect(INTERNAL_ERR) };
-				let outs: Vec<_> = f.outputs.iter().map(|param| from_token(&param.kind, &o)).collect();
+				let outs: Vec<_> = f.outputs.iter().map(|param| from_token(&param.kind, &o, types)).collect();
 
 				quote! {
 					let mut out = self.0.decode_output(output)?.into_iter();
@@ -132,23 +189,68 @@ impl<'a> From<&'a ethabi::Function> for Function {
 		#[allow(deprecated)]
 		Function {
 			name: f.name.clone(),
-			inputs: Inputs { tokenize, template_params, recreate_quote: to_ethabi_param_vec(&f.inputs) },
+			module_name,
+			signature: signature_of(&f.name, &f.inputs.iter().map(|p| p.kind.clone()).collect::<Vec<_>>()),
+			docs: Docs::default(),
+			selector: f.short_signature(),
+			inputs: Inputs {
+				tokenize,
+				template_params,
+				fields,
+				decode,
+				recreate_quote: to_ethabi_param_vec(&f.inputs),
+				params: f.inputs.clone(),
+			},
 			outputs: Outputs {
 				implementation: output_implementation,
 				result: output_result,
 				recreate_quote: to_ethabi_param_vec(&f.outputs),
+				params: f.outputs.clone(),
 			},
 			constant: f.constant.unwrap_or_default(),
 			state_mutability: f.state_mutability,
 		}
 	}
-}
 
-impl Function {
+	/// Function signature, e.g. `transfer(address,uint256)`.
+	pub fn signature(&self) -> &str {
+		&self.signature
+	}
+
+	/// Sets NatSpec documentation of the function.
+	pub fn set_docs(&mut self, docs: Docs) {
+		self.docs = docs;
+	}
+
+	/// Generates the variant of the contract's `Call` enum for this function.
+	pub fn generate_call_variant(&self) -> TokenStream {
+		let module_name = syn::Ident::new(&self.module_name, Span::call_site());
+		let variant = syn::Ident::new(&self.module_name.to_upper_camel_case(), Span::call_site());
+		let doc = format!(" Call to `{}`.", self.signature);
+
+		quote! {
+			#[doc = #doc]
+			#variant(#module_name::Inputs)
+		}
+	}
+
+	/// Generates the branch of `decode_call` which decodes input for this function.
+	pub fn generate_call_decoder(&self) -> TokenStream {
+		let module_name = syn::Ident::new(&self.module_name, Span::call_site());
+		let variant = syn::Ident::new(&self.module_name.to_upper_camel_case(), Span::call_site());
+		let selector = &self.selector;
+
+		quote! {
+			if selector == [#(#selector),*] {
+				return #module_name::decode_input(input).map(Call::#variant);
+			}
+		}
+	}
+
 	/// Generates the interface for contract's function.
 	pub fn generate(&self) -> TokenStream {
 		let name = &self.name;
-		let module_name = syn::Ident::new(&self.name.to_snake_case(), Span::call_site());
+		let module_name = syn::Ident::new(&self.module_name, Span::call_site());
 		let tokenize = &self.inputs.tokenize;
 		let declarations: &Vec<_> = &self.inputs.template_params.iter().map(|i| &i.declaration).collect();
 		let definitions: &Vec<_> = &self.inputs.template_params.iter().map(|i| &i.definition).collect();
@@ -164,8 +266,25 @@ impl Function {
 		};
 		let outputs_result = &self.outputs.result;
 		let outputs_implementation = &self.outputs.implementation;
+		let fields = &self.inputs.fields;
+		let field_docs = self.inputs.params.iter().enumerate().map(|(index, param)| {
+			let fallback = if param.name.is_empty() {
+				format!("Input {} of type `{}`.", index, param.kind)
+			} else {
+				format!("Input `{}` of type `{}`.", param.name, param.kind)
+			};
+			self.docs.param(&param.name, &fallback)
+		});
+		let decode = &self.inputs.decode;
+		let docs = self.docs.item(&format!("Function `{}`.", self.signature));
+		let param_names = || self.inputs.params.iter().map(|param| param.name.as_str());
+		let encode_input_docs = self.docs.with_params("Encodes function input.", param_names());
+		let decode_output_docs = self.docs.with_returns("Decodes function output.", &self.outputs.params);
+		let call_docs =
+			self.docs.with_params("Encodes function output and creates a `Decoder` instance.", param_names());
 
 		quote! {
+			#docs
 			pub mod #module_name {
 				use ethabi;
 				use super::INTERNAL_ERR;
@@ -191,19 +310,37 @@ impl Function {
 					}
 				}
 
-				/// Encodes function input.
+				#encode_input_docs
 				pub fn encode_input<#(#declarations),*>(#(#definitions),*) -> ethabi::Bytes {
 					let f = function();
 					let tokens = vec![#(#tokenize),*];
 					f.encode_input(&tokens).expect(INTERNAL_ERR)
 				}
 
-				/// Decodes function output.
+				#decode_output_docs
 				pub fn decode_output(output: &[u8]) -> ethabi::Result<#outputs_result> {
 					ethabi::FunctionOutputDecoder::decode(&Decoder(function()), output)
 				}
 
-				/// Encodes function output and creates a `Decoder` instance.
+				/// Decoded function input.
+				#[derive(Debug, Clone, PartialEq, Eq)]
+				pub struct Inputs {
+					#(#field_docs #fields),*
+				}
+
+				/// Decodes function input, checking that it starts with the function selector.
+				pub fn decode_input(input: &[u8]) -> ethabi::Result<Inputs> {
+					let f = function();
+					if input.len() < 4 || input[..4] != f.short_signature() {
+						return Err(ethabi::Error::InvalidData);
+					}
+					let mut tokens = f.decode_input(&input[4..])?.into_iter();
+					Ok(Inputs {
+						#(#decode),*
+					})
+				}
+
+				#call_docs
 				pub fn call<#(#declarations),*>(#(#definitions),*) -> (ethabi::Bytes, Decoder) {
 					let f = function();
 					let tokens = vec![#(#tokenize),*];
@@ -233,6 +370,7 @@ mod tests {
 		let f = Function::from(&ethabi_function);
 
 		let expected = quote! {
+			#[doc = " Function `empty()`."]
 			pub mod empty {
 				use ethabi;
 				use super::INTERNAL_ERR;
@@ -259,19 +397,33 @@ mod tests {
 					}
 				}
 
-				/// Encodes function input.
+				#[doc = " Encodes function input."]
 				pub fn encode_input<>() -> ethabi::Bytes {
 					let f = function();
 					let tokens = vec![];
 					f.encode_input(&tokens).expect(INTERNAL_ERR)
 				}
 
-				/// Decodes function output.
+				#[doc = " Decodes function output."]
 				pub fn decode_output(output: &[u8]) -> ethabi::Result<()> {
 					ethabi::FunctionOutputDecoder::decode(&Decoder(function()), output)
 				}
 
-				/// Encodes function output and creates a `Decoder` instance.
+				/// Decoded function input.
+				#[derive(Debug, Clone, PartialEq, Eq)]
+				pub struct Inputs {}
+
+				/// Decodes function input, checking that it starts with the function selector.
+				pub fn decode_input(input: &[u8]) -> ethabi::Result<Inputs> {
+					let f = function();
+					if input.len() < 4 || input[..4] != f.short_signature() {
+						return Err(ethabi::Error::InvalidData);
+					}
+					let mut tokens = f.decode_input(&input[4..])?.into_iter();
+					Ok(Inputs {})
+				}
+
+				#[doc = " Encodes function output and creates a `Decoder` instance."]
 				pub fn call<>() -> (ethabi::Bytes, Decoder) {
 					let f = function();
 					let tokens = vec![];
@@ -288,11 +440,17 @@ mod tests {
 		#[allow(deprecated)]
 		let ethabi_function = ethabi::Function {
 			name: "hello".into(),
-			inputs: vec![ethabi::Param { name: "foo".into(), kind: ethabi::ParamType::Address, internal_type: None }],
+			inputs: vec![ethabi::Param {
+				name: "foo".into(),
+				kind: ethabi::ParamType::Address,
+				internal_type: None,
+				components: vec![],
+			}],
 			outputs: vec![ethabi::Param {
 				name: "bar".into(),
 				kind: ethabi::ParamType::Uint(256),
 				internal_type: None,
+				components: vec![],
 			}],
 			constant: None,
 			state_mutability: ethabi::StateMutability::Payable,
@@ -301,6 +459,7 @@ mod tests {
 		let f = Function::from(&ethabi_function);
 
 		let expected = quote! {
+			#[doc = " Function `hello(address)`."]
 			pub mod hello {
 				use ethabi;
 				use super::INTERNAL_ERR;
@@ -311,12 +470,14 @@ mod tests {
 						inputs: vec![ethabi::Param {
 							name: "foo".to_owned(),
 							kind: ethabi::ParamType::Address,
-							internal_type: None
+							internal_type: None,
+							components: vec![]
 						}],
 						outputs: vec![ethabi::Param {
 							name: "bar".to_owned(),
 							kind: ethabi::ParamType::Uint(256usize),
-							internal_type: None
+							internal_type: None,
+							components: vec![]
 						}],
 						constant: Some(false),
 						state_mutability: ::ethabi::StateMutability::Payable
@@ -335,19 +496,38 @@ mod tests {
 					}
 				}
 
-				/// Encodes function input.
+				#[doc = " Encodes function input."]
 				pub fn encode_input<T0: Into<ethabi::Address> >(foo: T0) -> ethabi::Bytes {
 					let f = function();
 					let tokens = vec![ethabi::Token::Address(foo.into())];
 					f.encode_input(&tokens).expect(INTERNAL_ERR)
 				}
 
-				/// Decodes function output.
+				#[doc = " Decodes function output."]
 				pub fn decode_output(output: &[u8]) -> ethabi::Result<ethabi::Uint> {
 					ethabi::FunctionOutputDecoder::decode(&Decoder(function()), output)
 				}
 
-				/// Encodes function output and creates a `Decoder` instance.
+				/// Decoded function input.
+				#[derive(Debug, Clone, PartialEq, Eq)]
+				pub struct Inputs {
+					#[doc = " Input `foo` of type `address`."]
+					pub foo: ethabi::Address
+				}
+
+				/// Decodes function input, checking that it starts with the function selector.
+				pub fn decode_input(input: &[u8]) -> ethabi::Result<Inputs> {
+					let f = function();
+					if input.len() < 4 || input[..4] != f.short_signature() {
+						return Err(ethabi::Error::InvalidData);
+					}
+					let mut tokens = f.decode_input(&input[4..])?.into_iter();
+					Ok(Inputs {
+						foo: tokens.next().expect(INTERNAL_ERR).into_address().expect(INTERNAL_ERR)
+					})
+				}
+
+				#[doc = " Encodes function output and creates a `Decoder` instance."]
 				pub fn call<T0: Into<ethabi::Address> >(foo: T0) -> (ethabi::Bytes, Decoder) {
 					let f = function();
 					let tokens = vec![ethabi::Token::Address(foo.into())];
@@ -369,16 +549,28 @@ mod tests {
 					name: "foo".into(),
 					kind: ethabi::ParamType::FixedArray(Box::new(ethabi::ParamType::Address), 2),
 					internal_type: None,
+					components: vec![],
 				},
 				ethabi::Param {
 					name: "bar".into(),
 					kind: ethabi::ParamType::Array(Box::new(ethabi::ParamType::Uint(256))),
 					internal_type: None,
+					components: vec![],
 				},
 			],
 			outputs: vec![
-				ethabi::Param { name: "".into(), kind: ethabi::ParamType::Uint(256), internal_type: None },
-				ethabi::Param { name: "".into(), kind: ethabi::ParamType::String, internal_type: None },
+				ethabi::Param {
+					name: "".into(),
+					kind: ethabi::ParamType::Uint(256),
+					internal_type: None,
+					components: vec![],
+				},
+				ethabi::Param {
+					name: "".into(),
+					kind: ethabi::ParamType::String,
+					internal_type: None,
+					components: vec![],
+				},
 			],
 			constant: None,
 			state_mutability: ethabi::StateMutability::Payable,
@@ -387,6 +579,7 @@ mod tests {
 		let f = Function::from(&ethabi_function);
 
 		let expected = quote! {
+			#[doc = " Function `multi(address[2],uint256[])`."]
 			pub mod multi {
 				use ethabi;
 				use super::INTERNAL_ERR;
@@ -397,20 +590,24 @@ mod tests {
 						inputs: vec![ethabi::Param {
 							name: "foo".to_owned(),
 							kind: ethabi::ParamType::FixedArray(Box::new(ethabi::ParamType::Address), 2usize),
-							internal_type: None
+							internal_type: None,
+							components: vec![]
 						}, ethabi::Param {
 							name: "bar".to_owned(),
 							kind: ethabi::ParamType::Array(Box::new(ethabi::ParamType::Uint(256usize))),
-							internal_type: None
+							internal_type: None,
+							components: vec![]
 						}],
 						outputs: vec![ethabi::Param {
 							name: "".to_owned(),
 							kind: ethabi::ParamType::Uint(256usize),
-							internal_type: None
+							internal_type: None,
+							components: vec![]
 						}, ethabi::Param {
 							name: "".to_owned(),
 							kind: ethabi::ParamType::String,
-							internal_type: None
+							internal_type: None,
+							components: vec![]
 						}],
 						constant: Some(false),
 						state_mutability: ::ethabi::StateMutability::Payable
@@ -429,7 +626,7 @@ mod tests {
 					}
 				}
 
-				/// Encodes function input.
+				#[doc = " Encodes function input."]
 				pub fn encode_input<T0: Into<[U0; 2usize]>, U0: Into<ethabi::Address>, T1: IntoIterator<Item = U1>, U1: Into<ethabi::Uint> >(foo: T0, bar: T1) -> ethabi::Bytes {
 					let f = function();
 					let tokens = vec![{
@@ -442,12 +639,37 @@ mod tests {
 					f.encode_input(&tokens).expect(INTERNAL_ERR)
 				}
 
-				/// Decodes function output.
+				#[doc = " Decodes function output."]
 				pub fn decode_output(output: &[u8]) -> ethabi::Result<(ethabi::Uint, String)> {
 					ethabi::FunctionOutputDecoder::decode(&Decoder(function()), output)
 				}
 
-				/// Encodes function output and creates a `Decoder` instance.
+				/// Decoded function input.
+				#[derive(Debug, Clone, PartialEq, Eq)]
+				pub struct Inputs {
+					#[doc = " Input `foo` of type `address[2]`."]
+					pub foo: [ethabi::Address; 2usize],
+					#[doc = " Input `bar` of type `uint256[]`."]
+					pub bar: Vec<ethabi::Uint>
+				}
+
+				/// Decodes function input, checking that it starts with the function selector.
+				pub fn decode_input(input: &[u8]) -> ethabi::Result<Inputs> {
+					let f = function();
+					if input.len() < 4 || input[..4] != f.short_signature() {
+						return Err(ethabi::Error::InvalidData);
+					}
+					let mut tokens = f.decode_input(&input[4..])?.into_iter();
+					Ok(Inputs {
+						foo: {
+							let mut iter = tokens.next().expect(INTERNAL_ERR).into_fixed_array().expect(INTERNAL_ERR).into_iter().map(|inner| inner.into_address().expect(INTERNAL_ERR));
+							[iter.next().expect(INTERNAL_ERR), iter.next().expect(INTERNAL_ERR)]
+						},
+						bar: tokens.next().expect(INTERNAL_ERR).into_array().expect(INTERNAL_ERR).into_iter().map(|inner| inner.into_uint().expect(INTERNAL_ERR)).collect()
+					})
+				}
+
+				#[doc = " Encodes function output and creates a `Decoder` instance."]
 				pub fn call<T0: Into<[U0; 2usize]>, U0: Into<ethabi::Address>, T1: IntoIterator<Item = U1>, U1: Into<ethabi::Uint> >(foo: T0, bar: T1) -> (ethabi::Bytes, Decoder) {
 					let f = function();
 					let tokens = vec![{
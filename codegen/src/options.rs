@@ -0,0 +1,326 @@
+// Copyright 2015-2019 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::{
+	borrow::Cow,
+	collections::{BTreeMap, BTreeSet},
+};
+
+use ethabi::{param_type::Reader, Error, ParamType, Result};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use super::str_value_of_meta_item;
+
+/// How overloaded functions and events are told apart in generated code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overloads {
+	/// Suffix the name with the number of params, e.g. `safe_transfer_from_4`.
+	#[default]
+	Arity,
+	/// Suffix the name with the param types, e.g. `safe_transfer_from_address_address_uint256_bytes`.
+	Signature,
+}
+
+/// Code generation options, set with `#[ethabi_contract_options(...)]`.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+	/// Naming of overloaded functions and events.
+	pub overloads: Overloads,
+	/// Maps a function or event signature, e.g. `safeTransferFrom(address,address,uint256,bytes)`,
+	/// to the name of its generated module.
+	pub renames: BTreeMap<String, String>,
+	/// Functions to generate bindings for.
+	pub functions: Selection,
+	/// Events to generate bindings for.
+	pub events: Selection,
+	/// Visibility of the generated `functions`, `events` and `logs` modules and of `constructor`.
+	pub visibility: Option<TokenStream>,
+	/// Rust types used in place of the default ones.
+	pub types: TypeAliases,
+	/// Whether log structs derive `serde::Serialize` and `serde::Deserialize`.
+	pub serde: bool,
+}
+
+/// Selects functions or events by name or signature.
+#[derive(Debug, Clone, Default)]
+pub struct Selection {
+	/// If set, only these are selected.
+	pub only: Option<BTreeSet<String>>,
+	/// These are never selected.
+	pub skip: BTreeSet<String>,
+}
+
+impl Selection {
+	/// Returns whether the item with given name and signature, e.g. `transfer(address,uint256)`, is selected.
+	pub fn contains(&self, name: &str, signature: &str) -> bool {
+		let listed = |set: &BTreeSet<String>| set.contains(name) || set.contains(signature);
+		let only = match self.only {
+			Some(ref only) => listed(only),
+			None => true,
+		};
+		only && !listed(&self.skip)
+	}
+
+	fn names(&self) -> impl Iterator<Item = &String> {
+		self.only.iter().flatten().chain(&self.skip)
+	}
+}
+
+/// Rust types used in place of the default ones for elementary ABI types.
+///
+/// The type must implement `Into` the default type and `TryFrom` it. Decoding fails with
+/// `Error::InvalidData` if the conversion from the default type fails.
+///
+/// Generated input, call and log structs holding the type derive `Debug`, `Clone`, `PartialEq` and `Eq`,
+/// and `Serialize` and `Deserialize` with `serde = true`, so the type must implement those too.
+#[derive(Debug, Clone, Default)]
+pub struct TypeAliases(BTreeMap<String, TokenStream>);
+
+impl TypeAliases {
+	/// Uses `rust_type`, e.g. `u128`, for the elementary ABI type `abi_type`, e.g. `uint256`.
+	pub fn insert(&mut self, abi_type: &str, rust_type: &str) -> Result<()> {
+		// the reader treats unknown names as enums, so check that the name round-trips
+		let kind = match Reader::read(abi_type) {
+			Ok(kind) if kind.to_string() == abi_type || abi_type == "uint" || abi_type == "int" => kind,
+			_ => return Err(Error::Other(Cow::Owned(format!("`{abi_type}` is not an elementary ABI type")))),
+		};
+		let parsed: syn::Type = syn::parse_str(rust_type)
+			.map_err(|_| Error::Other(Cow::Owned(format!("Invalid type `{rust_type}` for `{abi_type}`"))))?;
+		if self.0.insert(kind.to_string(), parsed.into_token_stream()).is_some() {
+			return Err(Error::Other(Cow::Owned(format!("`{kind}` has more than one custom type"))));
+		}
+		Ok(())
+	}
+
+	/// Returns the type used for given ABI type.
+	pub fn get(&self, kind: &ParamType) -> Option<&TokenStream> {
+		self.0.get(&kind.to_string())
+	}
+
+	/// Returns whether given ABI type, or any of its elements, has a custom type.
+	pub fn is_used_by(&self, kind: &ParamType) -> bool {
+		match *kind {
+			ParamType::Array(ref inner) | ParamType::FixedArray(ref inner, _) => self.is_used_by(inner),
+			ParamType::Tuple(ref inner) => inner.iter().any(|kind| self.is_used_by(kind)),
+			ref kind => self.get(kind).is_some(),
+		}
+	}
+}
+
+impl Options {
+	/// Reads code generation options, ignoring `path` and `contract`.
+	pub fn parse(options: &[syn::NestedMeta]) -> Result<Self> {
+		let mut result = Options::default();
+
+		for meta in options.iter().filter_map(|nested| match *nested {
+			syn::NestedMeta::Meta(ref meta) => Some(meta),
+			_ => None,
+		}) {
+			if meta.path().is_ident("path") || meta.path().is_ident("contract") {
+				continue;
+			} else if meta.path().is_ident("overloads") {
+				result.overloads = match str_value_of_meta_item(meta, "overloads")?.as_str() {
+					"arity" => Overloads::Arity,
+					"signature" => Overloads::Signature,
+					other => {
+						return Err(Error::Other(Cow::Owned(format!(
+							"Unknown `overloads` value `{other}`, expected `arity` or `signature`"
+						))))
+					}
+				};
+			} else if meta.path().is_ident("rename") {
+				let (signature, name) = parse_rename(meta)?;
+				if result.renames.insert(signature.clone(), name).is_some() {
+					return Err(Error::Other(Cow::Owned(format!("`{signature}` is renamed more than once"))));
+				}
+			} else if meta.path().is_ident("functions") {
+				result.functions.only = Some(parse_list(meta, "functions")?);
+			} else if meta.path().is_ident("skip_functions") {
+				result.functions.skip = parse_list(meta, "skip_functions")?;
+			} else if meta.path().is_ident("events") {
+				result.events.only = Some(parse_list(meta, "events")?);
+			} else if meta.path().is_ident("skip_events") {
+				result.events.skip = parse_list(meta, "skip_events")?;
+			} else if meta.path().is_ident("visibility") {
+				result.set_visibility(&str_value_of_meta_item(meta, "visibility")?)?;
+			} else if meta.path().is_ident("types") {
+				result.types = parse_types(meta)?;
+			} else if meta.path().is_ident("serde") {
+				result.serde = bool_value_of_meta_item(meta, "serde")?;
+			} else {
+				let name = meta.path().get_ident().map(ToString::to_string).unwrap_or_default();
+				return Err(Error::Other(Cow::Owned(format!("Unknown option `{name}`"))));
+			}
+		}
+
+		Ok(result)
+	}
+
+	/// Returns names and signatures in `functions`, `skip_functions`, `events` and `skip_events`.
+	pub fn selected_names(&self) -> impl Iterator<Item = &String> {
+		self.functions.names().chain(self.events.names())
+	}
+
+	/// Sets the visibility of generated modules, e.g. `pub(crate)`.
+	pub fn set_visibility(&mut self, visibility: &str) -> Result<()> {
+		let parsed: syn::Visibility = syn::parse_str(visibility)
+			.map_err(|_| Error::Other(Cow::Owned(format!("Invalid visibility `{visibility}`"))))?;
+		self.visibility = Some(parsed.into_token_stream());
+		Ok(())
+	}
+
+	/// Returns the visibility of generated modules.
+	pub fn visibility(&self) -> TokenStream {
+		self.visibility.clone().unwrap_or_else(|| quote! { pub })
+	}
+}
+
+fn bool_value_of_meta_item(item: &syn::Meta, name: &str) -> Result<bool> {
+	if let syn::Meta::NameValue(ref name_value) = *item {
+		if let syn::Lit::Bool(ref value) = name_value.lit {
+			return Ok(value.value);
+		}
+	}
+
+	Err(Error::Other(Cow::Owned(format!("`{name}` must be in the form `{name} = true` or `{name} = false`"))))
+}
+
+fn parse_list(meta: &syn::Meta, name: &str) -> Result<BTreeSet<String>> {
+	let usage = || Error::Other(Cow::Owned(format!(r#"`{name}` must be in the form `{name}("foo", "bar(uint256)")`"#)));
+
+	let list = match *meta {
+		syn::Meta::List(ref list) => list,
+		_ => return Err(usage()),
+	};
+
+	list.nested
+		.iter()
+		.map(|nested| match *nested {
+			syn::NestedMeta::Lit(syn::Lit::Str(ref value)) => Ok(value.value().replace(' ', "")),
+			_ => Err(usage()),
+		})
+		.collect()
+}
+
+fn parse_types(meta: &syn::Meta) -> Result<TypeAliases> {
+	const USAGE: &str = r#"`types` must be in the form `types(address = "crate::Address", uint256 = "u128")`"#;
+
+	let list = match *meta {
+		syn::Meta::List(ref list) => list,
+		_ => return Err(Error::Other(Cow::Borrowed(USAGE))),
+	};
+
+	let mut types = TypeAliases::default();
+	for nested in &list.nested {
+		let meta = match *nested {
+			syn::NestedMeta::Meta(ref meta @ syn::Meta::NameValue(_)) => meta,
+			_ => return Err(Error::Other(Cow::Borrowed(USAGE))),
+		};
+		let abi_type = meta.path().get_ident().map(ToString::to_string).unwrap_or_default();
+		types.insert(&abi_type, &str_value_of_meta_item(meta, &abi_type)?)?;
+	}
+
+	Ok(types)
+}
+
+fn parse_rename(meta: &syn::Meta) -> Result<(String, String)> {
+	const USAGE: &str = r#"`rename` must be in the form `rename(signature = "foo(uint256)", name = "foo_uint")`"#;
+
+	let list = match *meta {
+		syn::Meta::List(ref list) => list,
+		_ => return Err(Error::Other(Cow::Borrowed(USAGE))),
+	};
+
+	let mut signature = None;
+	let mut name = None;
+	for nested in &list.nested {
+		match *nested {
+			syn::NestedMeta::Meta(ref meta) if meta.path().is_ident("signature") => {
+				signature = Some(str_value_of_meta_item(meta, "signature")?.replace(' ', ""));
+			}
+			syn::NestedMeta::Meta(ref meta) if meta.path().is_ident("name") => {
+				name = Some(str_value_of_meta_item(meta, "name")?);
+			}
+			_ => return Err(Error::Other(Cow::Borrowed(USAGE))),
+		}
+	}
+
+	match (signature, name) {
+		(Some(signature), Some(name)) => Ok((signature, name)),
+		_ => Err(Error::Other(Cow::Borrowed(USAGE))),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use ethabi::ParamType;
+
+	use super::{Options, Overloads};
+
+	fn parse(meta: syn::Meta) -> ethabi::Result<Options> {
+		match meta {
+			syn::Meta::List(list) => Options::parse(&list.nested.into_iter().collect::<Vec<_>>()),
+			_ => panic!("expected a list of options"),
+		}
+	}
+
+	#[test]
+	fn defaults() {
+		let options = parse(syn::parse_quote!(ethabi_contract_options(path = "foo.abi"))).unwrap();
+		assert_eq!(options.overloads, Overloads::Arity);
+		assert!(options.renames.is_empty());
+	}
+
+	#[test]
+	fn overloads_and_renames() {
+		let options = parse(syn::parse_quote!(ethabi_contract_options(
+			path = "foo.abi",
+			overloads = "signature",
+			rename(signature = "bar(bool, string)", name = "bar_with_string")
+		)))
+		.unwrap();
+		assert_eq!(options.overloads, Overloads::Signature);
+		assert_eq!(options.renames.get("bar(bool,string)").map(String::as_str), Some("bar_with_string"));
+	}
+
+	#[test]
+	fn selection_visibility_types_and_serde() {
+		let options = parse(syn::parse_quote!(ethabi_contract_options(
+			path = "foo.abi",
+			functions("transfer", "approve(address, uint256)"),
+			skip_events("Approval"),
+			visibility = "pub(crate)",
+			types(address = "crate::Address", uint = "u128"),
+			serde = true
+		)))
+		.unwrap();
+		assert!(options.functions.contains("transfer", "transfer(address,uint256)"));
+		assert!(options.functions.contains("approve", "approve(address,uint256)"));
+		assert!(!options.functions.contains("approve", "approve(address,uint128)"));
+		assert!(options.events.contains("Transfer", "Transfer(address,address,uint256)"));
+		assert!(!options.events.contains("Approval", "Approval(address,address,uint256)"));
+		assert_eq!(options.visibility().to_string(), "pub (crate)");
+		assert_eq!(options.types.get(&ParamType::Uint(256)).unwrap().to_string(), "u128");
+		assert!(options.types.is_used_by(&ParamType::Array(Box::new(ParamType::Address))));
+		assert!(!options.types.is_used_by(&ParamType::Uint(128)));
+		assert!(options.serde);
+	}
+
+	#[test]
+	fn invalid_options() {
+		assert!(parse(syn::parse_quote!(ethabi_contract_options(path = "foo.abi", overloads = "nope"))).is_err());
+		assert!(parse(syn::parse_quote!(ethabi_contract_options(path = "foo.abi", unknown = "x"))).is_err());
+		assert!(parse(syn::parse_quote!(ethabi_contract_options(path = "foo.abi", rename(name = "x")))).is_err());
+		assert!(parse(syn::parse_quote!(ethabi_contract_options(path = "foo.abi", visibility = "public"))).is_err());
+		assert!(parse(syn::parse_quote!(ethabi_contract_options(path = "foo.abi", functions = "foo"))).is_err());
+		assert!(parse(syn::parse_quote!(ethabi_contract_options(path = "foo.abi", types(foo = "u8")))).is_err());
+		assert!(parse(syn::parse_quote!(ethabi_contract_options(path = "foo.abi", types(address = "1")))).is_err());
+		assert!(parse(syn::parse_quote!(ethabi_contract_options(path = "foo.abi", serde = "yes"))).is_err());
+	}
+}
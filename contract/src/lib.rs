@@ -9,8 +9,8 @@
 #[macro_export]
 macro_rules! use_contract {
 	($module: ident, $path: expr) => {
+		#[doc = concat!("Bindings of the contract at `", $path, "`.")]
 		#[allow(dead_code)]
-		#[allow(missing_docs)]
 		#[allow(unused_imports)]
 		#[allow(unused_mut)]
 		#[allow(unused_variables)]
@@ -20,4 +20,16 @@ macro_rules! use_contract {
 			struct _Dummy;
 		}
 	};
+	($module: ident, $path: expr, $($options: tt)+) => {
+		#[doc = concat!("Bindings of the contract at `", $path, "`.")]
+		#[allow(dead_code)]
+		#[allow(unused_imports)]
+		#[allow(unused_mut)]
+		#[allow(unused_variables)]
+		pub mod $module {
+			#[derive(ethabi_derive::EthabiContract)]
+			#[ethabi_contract_options(path = $path, $($options)+)]
+			struct _Dummy;
+		}
+	};
 }
@@ -0,0 +1,278 @@
+// Copyright 2015-2020 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Contract artifacts produced by Hardhat, Foundry, Truffle and solc.
+
+use std::{collections::BTreeMap, fmt, io};
+
+use serde::{
+	de::{value::MapAccessDeserializer, DeserializeOwned, MapAccess, SeqAccess, Visitor},
+	Deserialize, Deserializer,
+};
+use serde_json::Value;
+
+use crate::{
+	contract::ContractVisitor,
+	errors,
+	natspec::{DevDoc, UserDoc},
+	Bytes, Contract,
+};
+
+/// Compiled contract: its abi along with the bytecode, metadata and documentation.
+///
+/// Deserializes from Hardhat, Foundry and Truffle artifacts, from contracts in solc standard json output,
+/// and from a plain abi.
+///
+/// Bytecode which still has unlinked library placeholders can't be decoded and is `None`,
+/// as is the bytecode of abstract contracts and interfaces.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Artifact {
+	/// Contract abi.
+	pub abi: Contract,
+	/// Creation bytecode.
+	pub bytecode: Option<Bytes>,
+	/// Runtime bytecode.
+	pub deployed_bytecode: Option<Bytes>,
+	/// Maps function signatures to hex encoded selectors, e.g. `transfer(address,uint256)` to `a9059cbb`.
+	pub method_identifiers: BTreeMap<String, String>,
+	/// Compiler metadata.
+	pub metadata: Option<Value>,
+	/// NatSpec end user documentation.
+	pub userdoc: Option<UserDoc>,
+	/// NatSpec developer documentation.
+	pub devdoc: Option<DevDoc>,
+}
+
+impl Artifact {
+	/// Loads artifact from json. A plain abi is accepted as well.
+	pub fn load<T: io::Read>(reader: T) -> errors::Result<Self> {
+		serde_json::from_reader(reader).map_err(From::from)
+	}
+}
+
+impl<'a> Deserialize<'a> for Artifact {
+	fn deserialize<D>(deserializer: D) -> Result<Artifact, D::Error>
+	where
+		D: Deserializer<'a>,
+	{
+		deserializer.deserialize_any(ArtifactVisitor)
+	}
+}
+
+struct ArtifactVisitor;
+
+impl<'a> Visitor<'a> for ArtifactVisitor {
+	type Value = Artifact;
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		formatter.write_str("contract artifact or abi spec file")
+	}
+
+	fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+	where
+		A: SeqAccess<'a>,
+	{
+		Ok(Artifact { abi: ContractVisitor.visit_seq(seq)?, ..Default::default() })
+	}
+
+	fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+	where
+		A: MapAccess<'a>,
+	{
+		let raw = RawArtifact::deserialize(MapAccessDeserializer::new(map))?;
+		let metadata = match (raw.metadata, raw.raw_metadata) {
+			// Truffle and solc keep metadata as a json string, Foundry has both forms
+			(Some(Value::String(metadata)), _) | (None, Some(metadata)) => {
+				Some(serde_json::from_str(&metadata).unwrap_or(Value::String(metadata)))
+			}
+			(metadata, _) => metadata,
+		};
+		// solc standard json output nests these under `evm`
+		let evm = raw.evm.unwrap_or_default();
+		Ok(Artifact {
+			abi: raw.abi,
+			bytecode: raw.bytecode.or(evm.bytecode).and_then(RawBytecode::decode),
+			deployed_bytecode: raw.deployed_bytecode.or(evm.deployed_bytecode).and_then(RawBytecode::decode),
+			method_identifiers: if raw.method_identifiers.is_empty() {
+				evm.method_identifiers
+			} else {
+				raw.method_identifiers
+			},
+			userdoc: raw.userdoc.or_else(|| metadata_doc(metadata.as_ref(), "userdoc")),
+			devdoc: raw.devdoc.or_else(|| metadata_doc(metadata.as_ref(), "devdoc")),
+			metadata,
+		})
+	}
+}
+
+/// Documentation is also part of the metadata, which is all that Foundry and Truffle keep of it.
+fn metadata_doc<T: DeserializeOwned>(metadata: Option<&Value>, key: &str) -> Option<T> {
+	let doc = metadata?.get("output")?.get(key)?;
+	serde_json::from_value(doc.clone()).ok()
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawArtifact {
+	abi: Contract,
+	#[serde(default)]
+	bytecode: Option<RawBytecode>,
+	#[serde(default)]
+	deployed_bytecode: Option<RawBytecode>,
+	#[serde(default)]
+	method_identifiers: BTreeMap<String, String>,
+	#[serde(default)]
+	metadata: Option<Value>,
+	#[serde(default)]
+	raw_metadata: Option<String>,
+	#[serde(default)]
+	evm: Option<RawEvm>,
+	#[serde(default)]
+	userdoc: Option<UserDoc>,
+	#[serde(default)]
+	devdoc: Option<DevDoc>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawEvm {
+	#[serde(default)]
+	bytecode: Option<RawBytecode>,
+	#[serde(default)]
+	deployed_bytecode: Option<RawBytecode>,
+	#[serde(default)]
+	method_identifiers: BTreeMap<String, String>,
+}
+
+/// Hardhat and Truffle use a hex string, Foundry an object with the hex string under `object`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawBytecode {
+	Hex(String),
+	Object { object: String },
+}
+
+impl RawBytecode {
+	fn decode(self) -> Option<Bytes> {
+		let hex = match self {
+			RawBytecode::Hex(hex) | RawBytecode::Object { object: hex } => hex,
+		};
+		let hex = hex.strip_prefix("0x").unwrap_or(&hex);
+		match hex::decode(hex) {
+			Ok(bytes) if !bytes.is_empty() => Some(bytes),
+			_ => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use hex_literal::hex;
+	use serde_json::json;
+
+	use super::Artifact;
+
+	#[test]
+	fn plain_abi() {
+		let artifact: Artifact =
+			serde_json::from_str(r#"[{ "type": "receive", "stateMutability": "payable" }]"#).unwrap();
+		assert!(artifact.abi.receive);
+		assert_eq!(artifact.bytecode, None);
+		assert_eq!(artifact.metadata, None);
+	}
+
+	#[test]
+	fn hardhat() {
+		let artifact: Artifact = serde_json::from_str(
+			r#"{
+				"_format": "hh-sol-artifact-1",
+				"contractName": "Counter",
+				"sourceName": "contracts/Counter.sol",
+				"abi": [{ "type": "function", "name": "increment", "inputs": [], "outputs": [], "stateMutability": "nonpayable" }],
+				"bytecode": "0x6080604052",
+				"deployedBytecode": "0x60806040",
+				"linkReferences": {},
+				"deployedLinkReferences": {}
+			}"#,
+		)
+		.unwrap();
+
+		assert!(artifact.abi.function("increment").is_ok());
+		assert_eq!(artifact.bytecode, Some(hex!("6080604052").to_vec()));
+		assert_eq!(artifact.deployed_bytecode, Some(hex!("60806040").to_vec()));
+		assert!(artifact.method_identifiers.is_empty());
+	}
+
+	#[test]
+	fn foundry() {
+		let artifact: Artifact = serde_json::from_str(
+			r#"{
+				"abi": [{ "type": "function", "name": "increment", "inputs": [], "outputs": [], "stateMutability": "nonpayable" }],
+				"bytecode": { "object": "0x6080604052", "sourceMap": "", "linkReferences": {} },
+				"deployedBytecode": { "object": "0x60806040", "sourceMap": "", "linkReferences": {} },
+				"methodIdentifiers": { "increment()": "d09de08a" },
+				"rawMetadata": "{\"compiler\":{\"version\":\"0.8.17\"}}",
+				"metadata": { "compiler": { "version": "0.8.17" } }
+			}"#,
+		)
+		.unwrap();
+
+		assert_eq!(artifact.bytecode, Some(hex!("6080604052").to_vec()));
+		assert_eq!(artifact.deployed_bytecode, Some(hex!("60806040").to_vec()));
+		assert_eq!(artifact.method_identifiers.get("increment()").map(String::as_str), Some("d09de08a"));
+		assert_eq!(artifact.metadata, Some(json!({ "compiler": { "version": "0.8.17" } })));
+	}
+
+	#[test]
+	fn truffle() {
+		let artifact: Artifact = serde_json::from_str(
+			r#"{
+				"contractName": "Counter",
+				"abi": [],
+				"metadata": "{\"compiler\":{\"version\":\"0.8.17\"}}",
+				"bytecode": "0x6080604052__$0123456789abcdef0123456789abcdef01$__",
+				"deployedBytecode": "0x"
+			}"#,
+		)
+		.unwrap();
+
+		// unlinked and empty bytecode
+		assert_eq!(artifact.bytecode, None);
+		assert_eq!(artifact.deployed_bytecode, None);
+		assert_eq!(artifact.metadata, Some(json!({ "compiler": { "version": "0.8.17" } })));
+	}
+
+	#[test]
+	fn solc() {
+		let artifact: Artifact = serde_json::from_str(
+			r#"{
+				"abi": [],
+				"evm": {
+					"bytecode": { "object": "6080604052", "opcodes": "", "sourceMap": "", "linkReferences": {} },
+					"deployedBytecode": { "object": "60806040" },
+					"methodIdentifiers": { "increment()": "d09de08a" }
+				},
+				"metadata": "{\"output\":{\"userdoc\":{\"notice\":\"Counts.\"}}}",
+				"devdoc": { "kind": "dev", "version": 1, "title": "Counter" }
+			}"#,
+		)
+		.unwrap();
+
+		assert_eq!(artifact.bytecode, Some(hex!("6080604052").to_vec()));
+		assert_eq!(artifact.deployed_bytecode, Some(hex!("60806040").to_vec()));
+		assert_eq!(artifact.method_identifiers.get("increment()").map(String::as_str), Some("d09de08a"));
+		assert_eq!(artifact.devdoc.unwrap().title.as_deref(), Some("Counter"));
+		// picked up from the metadata
+		assert_eq!(artifact.userdoc.unwrap().notice.as_deref(), Some("Counts."));
+	}
+
+	#[test]
+	fn missing_abi() {
+		assert!(serde_json::from_str::<Artifact>(r#"{ "bytecode": "0x00" }"#).is_err());
+	}
+}
@@ -0,0 +1,87 @@
+// Copyright 2015-2020 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Mixed case address checksums.
+
+use sha3::{Digest, Keccak256};
+
+#[cfg(not(feature = "std"))]
+use crate::no_std_prelude::*;
+use crate::Address;
+
+/// Formats an address as `0x` prefixed hex with the EIP-55 mixed case checksum, or with the chain aware
+/// EIP-1191 checksum for given `chain_id`.
+pub fn checksum_address(address: &Address, chain_id: Option<u64>) -> String {
+	let hex = hex::encode(address.as_bytes());
+	let hash = match chain_id {
+		Some(chain_id) => Keccak256::digest(format!("{chain_id}0x{hex}").as_bytes()),
+		None => Keccak256::digest(hex.as_bytes()),
+	};
+	let checksummed: String = hex
+		.char_indices()
+		.map(|(i, c)| {
+			let nibble = if i % 2 == 0 { hash[i / 2] >> 4 } else { hash[i / 2] & 0x0f };
+			if nibble >= 8 {
+				c.to_ascii_uppercase()
+			} else {
+				c
+			}
+		})
+		.collect();
+	format!("0x{checksummed}")
+}
+
+/// Checks the EIP-55 checksum of `hex`, an address without `0x` prefix. All lowercase or all uppercase hex carries
+/// no checksum and always passes.
+#[cfg(feature = "full-serde")]
+pub(crate) fn is_valid_checksum(hex: &str, address: &Address) -> bool {
+	let has_lower = hex.chars().any(|c| c.is_ascii_lowercase());
+	let has_upper = hex.chars().any(|c| c.is_ascii_uppercase());
+	!(has_lower && has_upper) || checksum_address(address, None)[2..] == *hex
+}
+
+#[cfg(test)]
+mod tests {
+	use super::checksum_address;
+
+	// test vectors of EIP-55
+	const CHECKSUMMED: [&str; 4] = [
+		"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+		"0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+		"0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+		"0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+	];
+
+	#[test]
+	fn eip55() {
+		for checksummed in CHECKSUMMED {
+			assert_eq!(checksum_address(&checksummed.parse().unwrap(), None), checksummed);
+		}
+	}
+
+	#[cfg(feature = "full-serde")]
+	#[test]
+	fn validate() {
+		use super::is_valid_checksum;
+
+		for checksummed in CHECKSUMMED {
+			let address = checksummed.parse().unwrap();
+			assert!(is_valid_checksum(&checksummed[2..], &address));
+			assert!(is_valid_checksum(&checksummed[2..].to_lowercase(), &address));
+			assert!(is_valid_checksum(&checksummed[2..].to_uppercase(), &address));
+			assert!(!is_valid_checksum(&checksummed[2..].replacen('a', "A", 1), &address));
+		}
+	}
+
+	#[test]
+	fn eip1191() {
+		let address = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".parse().unwrap();
+		assert_eq!(checksum_address(&address, Some(30)), "0x5aaEB6053f3e94c9b9a09f33669435E7ef1bEAeD");
+		assert_eq!(checksum_address(&address, Some(31)), "0x5aAeb6053F3e94c9b9A09F33669435E7EF1BEaEd");
+	}
+}
@@ -15,7 +15,7 @@ use std::io;
 
 #[cfg(feature = "serde")]
 use serde::{
-	de::{SeqAccess, Visitor},
+	de::{self, IgnoredAny, MapAccess, SeqAccess, Visitor},
 	ser::SerializeSeq,
 	Deserialize, Deserializer, Serialize, Serializer,
 };
@@ -24,7 +24,7 @@ use serde::{
 use crate::no_std_prelude::*;
 #[cfg(feature = "serde")]
 use crate::operation::Operation;
-use crate::{error::Error as AbiError, errors, Constructor, Error, Event, Function};
+use crate::{error::Error as AbiError, errors, Constructor, Error, Event, Function, Lint};
 
 /// API building calls to contracts ABI.
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -54,14 +54,34 @@ impl<'a> Deserialize<'a> for Contract {
 }
 
 #[cfg(feature = "serde")]
-struct ContractVisitor;
+pub(crate) struct ContractVisitor;
 
 #[cfg(feature = "serde")]
 impl<'a> Visitor<'a> for ContractVisitor {
 	type Value = Contract;
 
 	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-		formatter.write_str("valid abi spec file")
+		formatter.write_str("valid abi spec file or contract artifact")
+	}
+
+	fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+	where
+		A: MapAccess<'a>,
+	{
+		// Hardhat, Foundry and Truffle artifacts keep the abi under the `abi` key
+		let mut abi = None;
+		while let Some(key) = map.next_key::<String>()? {
+			if key == "abi" {
+				if abi.is_some() {
+					return Err(de::Error::duplicate_field("abi"));
+				}
+				abi = Some(map.next_value()?);
+			} else {
+				map.next_value::<IgnoredAny>()?;
+			}
+		}
+
+		abi.ok_or_else(|| de::Error::missing_field("abi"))
 	}
 
 	fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
@@ -162,7 +182,7 @@ impl Serialize for Contract {
 }
 
 impl Contract {
-	/// Loads contract from json.
+	/// Loads contract from json, either an abi or a contract artifact with an `abi` key.
 	#[cfg(feature = "full-serde")]
 	pub fn load<T: io::Read>(reader: T) -> errors::Result<Self> {
 		serde_json::from_reader(reader).map_err(From::from)
@@ -218,6 +238,12 @@ impl Contract {
 	pub fn errors(&self) -> AbiErrors {
 		AbiErrors(self.errors.values().flatten())
 	}
+
+	/// Checks the contract for 4-byte selector collisions between functions and errors,
+	/// overloads that lenient tokenization cannot tell apart, and events sharing a topic0.
+	pub fn lint(&self) -> Vec<Lint> {
+		crate::lint::lint(self)
+	}
 }
 
 /// Contract functions iterator.
@@ -306,7 +332,12 @@ mod test {
 			deserialized,
 			Contract {
 				constructor: Some(Constructor {
-					inputs: vec![Param { name: "a".to_string(), kind: ParamType::Address, internal_type: None }]
+					inputs: vec![Param {
+						name: "a".to_string(),
+						kind: ParamType::Address,
+						internal_type: None,
+						components: vec![]
+					}]
 				}),
 				functions: BTreeMap::new(),
 				events: BTreeMap::new(),
@@ -363,11 +394,13 @@ mod test {
 								name: "a".to_string(),
 								kind: ParamType::Address,
 								internal_type: None,
+								components: vec![],
 							}],
 							outputs: vec![Param {
 								name: "res".to_string(),
 								kind: ParamType::Address,
 								internal_type: None,
+								components: vec![],
 							}],
 							constant: None,
 							state_mutability: Default::default(),
@@ -438,11 +471,13 @@ mod test {
 								name: "a".to_string(),
 								kind: ParamType::Address,
 								internal_type: None,
+								components: vec![],
 							}],
 							outputs: vec![Param {
 								name: "res".to_string(),
 								kind: ParamType::Address,
 								internal_type: None,
+								components: vec![],
 							}],
 							constant: None,
 							state_mutability: Default::default(),
@@ -512,6 +547,8 @@ mod test {
 								name: "a".to_string(),
 								kind: ParamType::Address,
 								indexed: false,
+								internal_type: None,
+								components: vec![],
 							}],
 							anonymous: false,
 						}]
@@ -520,7 +557,13 @@ mod test {
 						"bar".to_string(),
 						vec![Event {
 							name: "bar".to_string(),
-							inputs: vec![EventParam { name: "a".to_string(), kind: ParamType::Address, indexed: true }],
+							inputs: vec![EventParam {
+								name: "a".to_string(),
+								kind: ParamType::Address,
+								indexed: true,
+								internal_type: None,
+								components: vec![]
+							}],
 							anonymous: false,
 						}]
 					),
@@ -580,12 +623,20 @@ mod test {
 								name: "a".to_string(),
 								kind: ParamType::Address,
 								indexed: false,
+								internal_type: None,
+								components: vec![],
 							}],
 							anonymous: false,
 						},
 						Event {
 							name: "foo".to_string(),
-							inputs: vec![EventParam { name: "a".to_string(), kind: ParamType::Address, indexed: true }],
+							inputs: vec![EventParam {
+								name: "a".to_string(),
+								kind: ParamType::Address,
+								indexed: true,
+								internal_type: None,
+								components: vec![]
+							}],
 							anonymous: false,
 						},
 					]
@@ -652,8 +703,14 @@ mod test {
 									name: "available".to_string(),
 									kind: ParamType::Uint(256),
 									internal_type: None,
+									components: vec![],
 								},
-								Param { name: "required".to_string(), kind: ParamType::Address, internal_type: None }
+								Param {
+									name: "required".to_string(),
+									kind: ParamType::Address,
+									internal_type: None,
+									components: vec![]
+								}
 							],
 						}]
 					),
@@ -662,8 +719,18 @@ mod test {
 						vec![AbiError {
 							name: "bar".to_string(),
 							inputs: vec![
-								Param { name: "a".to_string(), kind: ParamType::Uint(256), internal_type: None },
-								Param { name: "b".to_string(), kind: ParamType::Address, internal_type: None }
+								Param {
+									name: "a".to_string(),
+									kind: ParamType::Uint(256),
+									internal_type: None,
+									components: vec![]
+								},
+								Param {
+									name: "b".to_string(),
+									kind: ParamType::Address,
+									internal_type: None,
+									components: vec![]
+								}
 							],
 						}]
 					),
@@ -724,13 +791,24 @@ mod test {
 								name: "a".to_string(),
 								kind: ParamType::Uint(256),
 								internal_type: None,
+								components: vec![],
 							}],
 						},
 						AbiError {
 							name: "foo".to_string(),
 							inputs: vec![
-								Param { name: "a".to_string(), kind: ParamType::Uint(256), internal_type: None },
-								Param { name: "b".to_string(), kind: ParamType::Address, internal_type: None }
+								Param {
+									name: "a".to_string(),
+									kind: ParamType::Uint(256),
+									internal_type: None,
+									components: vec![]
+								},
+								Param {
+									name: "b".to_string(),
+									kind: ParamType::Address,
+									internal_type: None,
+									components: vec![]
+								}
 							],
 						},
 					]
@@ -792,4 +870,22 @@ mod test {
 
 		assert_ser_de(&deserialized);
 	}
+
+	#[test]
+	fn artifact() {
+		let json = r#"
+			{
+				"contractName": "Fallback",
+				"abi": [
+					{ "type": "fallback" }
+				],
+				"bytecode": "0x00"
+			}
+		"#;
+
+		let deserialized: Contract = serde_json::from_str(json).unwrap();
+		assert!(deserialized.fallback);
+
+		assert!(serde_json::from_str::<Contract>(r#"{ "bytecode": "0x00" }"#).is_err());
+	}
 }
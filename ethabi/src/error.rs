@@ -15,8 +15,8 @@ use serde::{Deserialize, Serialize};
 use crate::no_std_prelude::*;
 use crate::{
 	decode, encode, errors,
-	signature::{long_signature, short_signature},
-	Bytes, Hash, Param, ParamType, Result, Token,
+	signature::{canonical_signature, long_signature, short_signature},
+	Bytes, Hash, NamedToken, Param, ParamType, Result, Token,
 };
 
 /// Contract error specification.
@@ -41,6 +41,11 @@ impl Error {
 		long_signature(&self.name, &self.param_types())
 	}
 
+	/// Returns the signature the selector is hashed from, e.g. `InsufficientBalance(uint256,uint256)`.
+	pub fn canonical_signature(&self) -> String {
+		canonical_signature(&self.name, &self.param_types())
+	}
+
 	/// Prepares ABI error with given input params.
 	pub fn encode(&self, tokens: &[Token]) -> Result<Bytes> {
 		let params = self.param_types();
@@ -58,4 +63,9 @@ impl Error {
 	pub fn decode(&self, data: &[u8]) -> Result<Vec<Token>> {
 		decode(&self.param_types(), data)
 	}
+
+	/// Parses the ABI error input, keeping the names of inputs and their tuple components.
+	pub fn decode_named(&self, data: &[u8]) -> Result<NamedToken> {
+		Ok(NamedToken::params(&self.inputs, self.decode(data)?))
+	}
 }
@@ -17,8 +17,10 @@ use sha3::{Digest, Keccak256};
 #[cfg(not(feature = "std"))]
 use crate::no_std_prelude::*;
 use crate::{
-	decode, decode_validate, encode, signature::long_signature, Error, EventParam, Hash, Log, LogParam, ParamType,
-	RawLog, RawTopicFilter, Result, Token, Topic, TopicFilter,
+	decode, decode_validate, encode,
+	signature::{canonical_signature, long_signature},
+	Error, EventParam, Hash, Log, LogParam, NamedToken, ParamType, RawLog, RawTopicFilter, Result, Token, Topic,
+	TopicFilter,
 };
 
 /// Contract event.
@@ -55,19 +57,50 @@ impl Event {
 		long_signature(&self.name, &self.param_types())
 	}
 
+	/// Returns the signature topic0 is hashed from, e.g. `Transfer(address,address,uint256)`.
+	pub fn canonical_signature(&self) -> String {
+		canonical_signature(&self.name, &self.param_types())
+	}
+
 	/// Creates topic filter
 	pub fn filter(&self, raw: RawTopicFilter) -> Result<TopicFilter> {
+		// Strings, bytes, arrays and structs are hashed, see
+		// https://docs.soliditylang.org/en/latest/abi-spec.html#encoding-of-indexed-event-parameters
 		fn convert_token(token: Token, kind: &ParamType) -> Result<Hash> {
 			if !token.type_check(kind) {
 				return Err(Error::InvalidData);
 			}
-			let encoded = encode(&[token]);
-			if encoded.len() == 32 {
-				let mut data = [0u8; 32];
-				data.copy_from_slice(&encoded);
-				Ok(data.into())
-			} else {
-				Ok(Hash::from_slice(Keccak256::digest(&encoded).as_slice()))
+			match kind {
+				ParamType::String
+				| ParamType::Bytes
+				| ParamType::Array(_)
+				| ParamType::FixedArray(_, _)
+				| ParamType::Tuple(_) => {
+					let mut encoded = Vec::new();
+					encode_in_place(&token, false, &mut encoded);
+					Ok(Hash::from_slice(Keccak256::digest(&encoded).as_slice()))
+				}
+				_ => Ok(Hash::from_slice(&encode(&[token]))),
+			}
+		}
+
+		// Contents of strings and bytes are padded to words only inside of arrays and structs, whose
+		// elements are encoded in place without offsets or lengths.
+		fn encode_in_place(token: &Token, nested: bool, out: &mut Vec<u8>) {
+			match token {
+				Token::String(s) => pad_in_place(s.as_bytes(), nested, out),
+				Token::Bytes(bytes) => pad_in_place(bytes, nested, out),
+				Token::Array(tokens) | Token::FixedArray(tokens) | Token::Tuple(tokens) => {
+					tokens.iter().for_each(|token| encode_in_place(token, true, out))
+				}
+				token => out.extend(encode(core::slice::from_ref(token))),
+			}
+		}
+
+		fn pad_in_place(bytes: &[u8], pad: bool, out: &mut Vec<u8>) {
+			out.extend_from_slice(bytes);
+			if pad {
+				out.resize(out.len() + (32 - bytes.len() % 32) % 32, 0);
 			}
 		}
 
@@ -186,6 +219,14 @@ impl Event {
 	pub fn parse_log(&self, log: RawLog) -> Result<Log> {
 		self.parse_log_inner(log, decode)
 	}
+
+	/// Parses `RawLog`, keeping the names of params and their tuple components.
+	///
+	/// Indexed params of dynamic types are only known by their hash.
+	pub fn parse_log_named(&self, log: RawLog) -> Result<NamedToken> {
+		let tokens = self.parse_log(log)?.params.into_iter().map(|param| param.value).collect();
+		Ok(NamedToken::tuple(self.inputs.iter().map(|input| (input.name.as_str(), &input.components[..])), tokens))
+	}
 }
 
 #[cfg(test)]
@@ -198,28 +239,104 @@ mod tests {
 		log::{Log, RawLog},
 		signature::long_signature,
 		token::Token,
-		Event, EventParam, Int, LogParam, ParamType,
+		Event, EventParam, Hash, Int, LogParam, NamedToken, ParamType, RawTopicFilter, Topic, TupleParam,
 	};
 
+	#[test]
+	fn filter_hashes_dynamic_values() {
+		use sha3::{Digest, Keccak256};
+
+		let param = |name: &str, kind: ParamType| EventParam {
+			name: name.to_owned(),
+			kind,
+			indexed: true,
+			internal_type: None,
+			components: vec![],
+		};
+		let event = Event {
+			name: "foo".to_owned(),
+			inputs: vec![
+				param("a", ParamType::String),
+				param("b", ParamType::Array(Box::new(ParamType::String))),
+				param("c", ParamType::Uint(256)),
+			],
+			anonymous: false,
+		};
+
+		let filter = event
+			.filter(RawTopicFilter {
+				topic0: Topic::This(Token::String("hello".into())),
+				topic1: Topic::OneOf(vec![Token::Array(vec![Token::String("ab".into()), Token::String("c".into())])]),
+				topic2: Topic::Any,
+			})
+			.unwrap();
+
+		// elements of arrays are padded to words, without offsets or lengths
+		let mut elements = [0u8; 64];
+		elements[..2].copy_from_slice(b"ab");
+		elements[32] = b'c';
+		assert_eq!(filter.topic0, Topic::This(event.signature()));
+		assert_eq!(
+			filter.topic1,
+			Topic::This(hex!("1c8aff950685c2ed4bc3174f3472287b56d9517b9c948127319a09a7a36deac8").into())
+		);
+		assert_eq!(filter.topic2, Topic::OneOf(vec![Hash::from_slice(&Keccak256::digest(elements))]));
+		assert_eq!(filter.topic3, Topic::Any);
+	}
+
 	#[test]
 	fn test_decoding_event() {
 		let event = Event {
 			name: "foo".to_owned(),
 			inputs: vec![
-				EventParam { name: "a".to_owned(), kind: ParamType::Int(256), indexed: false },
-				EventParam { name: "b".to_owned(), kind: ParamType::Int(256), indexed: true },
-				EventParam { name: "c".to_owned(), kind: ParamType::Address, indexed: false },
-				EventParam { name: "d".to_owned(), kind: ParamType::Address, indexed: true },
-				EventParam { name: "e".to_owned(), kind: ParamType::String, indexed: true },
+				EventParam {
+					name: "a".to_owned(),
+					kind: ParamType::Int(256),
+					indexed: false,
+					internal_type: None,
+					components: vec![],
+				},
+				EventParam {
+					name: "b".to_owned(),
+					kind: ParamType::Int(256),
+					indexed: true,
+					internal_type: None,
+					components: vec![],
+				},
+				EventParam {
+					name: "c".to_owned(),
+					kind: ParamType::Address,
+					indexed: false,
+					internal_type: None,
+					components: vec![],
+				},
+				EventParam {
+					name: "d".to_owned(),
+					kind: ParamType::Address,
+					indexed: true,
+					internal_type: None,
+					components: vec![],
+				},
+				EventParam {
+					name: "e".to_owned(),
+					kind: ParamType::String,
+					indexed: true,
+					internal_type: None,
+					components: vec![],
+				},
 				EventParam {
 					name: "f".to_owned(),
 					kind: ParamType::Array(Box::new(ParamType::Int(256))),
 					indexed: true,
+					internal_type: None,
+					components: vec![],
 				},
 				EventParam {
 					name: "g".to_owned(),
 					kind: ParamType::FixedArray(Box::new(ParamType::Address), 5),
 					indexed: true,
+					internal_type: None,
+					components: vec![],
 				},
 			],
 			anonymous: false,
@@ -309,8 +426,16 @@ mod tests {
 					name: "tuple".into(),
 					kind: ParamType::Tuple(vec![ParamType::Address, ParamType::Address]),
 					indexed: false,
+					internal_type: None,
+					components: vec![],
+				},
+				EventParam {
+					name: "addr".into(),
+					kind: ParamType::Address,
+					indexed: true,
+					internal_type: None,
+					components: vec![],
 				},
-				EventParam { name: "addr".into(), kind: ParamType::Address, indexed: true },
 			],
 			anonymous: false,
 		};
@@ -337,4 +462,54 @@ mod tests {
 		assert!(wrong_event.parse_log_validate(log.clone()).is_err());
 		assert!(correct_event.parse_log_validate(log).is_ok());
 	}
+
+	#[test]
+	fn parse_log_named() {
+		let component = |name: &str| TupleParam {
+			name: Some(name.to_owned()),
+			kind: ParamType::Address,
+			internal_type: None,
+			components: vec![],
+		};
+		let event = Event {
+			name: "Test".into(),
+			inputs: vec![
+				EventParam {
+					name: "tuple".into(),
+					kind: ParamType::Tuple(vec![ParamType::Address, ParamType::Address]),
+					indexed: false,
+					internal_type: Some("struct Pair".into()),
+					components: vec![component("from"), component("to")],
+				},
+				EventParam {
+					name: "addr".into(),
+					kind: ParamType::Address,
+					indexed: true,
+					internal_type: None,
+					components: vec![],
+				},
+			],
+			anonymous: false,
+		};
+
+		let log = RawLog {
+			topics: vec![
+				hex!("cf74b4e62f836eeedcd6f92120ffb5afea90e6fa490d36f8b81075e2a7de0cf7").into(),
+				hex!("0000000000000000000000000000000000000000000000000000000000012321").into(),
+			],
+			data: hex!(
+				"
+			0000000000000000000000000000000000000000000000000000000000012345
+			0000000000000000000000000000000000000000000000000000000000054321
+			"
+			)
+			.into(),
+		};
+		let named = event.parse_log_named(log).unwrap();
+
+		let to = named.get("tuple").and_then(|tuple| tuple.get("to")).and_then(NamedToken::as_token);
+		assert_eq!(to, Some(&Token::Address(hex!("0000000000000000000000000000000000054321").into())));
+		let addr = named.get("addr").and_then(NamedToken::as_token);
+		assert_eq!(addr, Some(&Token::Address(hex!("0000000000000000000000000000000000012321").into())));
+	}
 }
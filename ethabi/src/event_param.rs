@@ -20,9 +20,9 @@ use serde::{
 
 #[cfg(not(feature = "std"))]
 use crate::no_std_prelude::*;
-use crate::ParamType;
 #[cfg(feature = "serde")]
-use crate::{param_type::Writer, TupleParam};
+use crate::param_type::Writer;
+use crate::{InternalType, ParamType, TupleParam};
 
 /// Event param specification.
 #[derive(Debug, Clone, PartialEq)]
@@ -33,6 +33,17 @@ pub struct EventParam {
 	pub kind: ParamType,
 	/// Indexed flag. If true, param is used to build block bloom.
 	pub indexed: bool,
+	/// Additional Internal type.
+	pub internal_type: Option<String>,
+	/// Components of the tuple in `kind`, if it is or contains one, keeping their names and internal types.
+	pub components: Vec<TupleParam>,
+}
+
+impl EventParam {
+	/// Parses `internal_type`, telling structs, enums, contracts and user defined value types apart.
+	pub fn internal_type(&self) -> Option<InternalType> {
+		self.internal_type.as_deref().map(InternalType::parse)
+	}
 }
 
 #[cfg(feature = "serde")]
@@ -64,6 +75,7 @@ impl<'a> Visitor<'a> for EventParamVisitor {
 		let mut kind = None;
 		let mut indexed = None;
 		let mut components = None;
+		let mut internal_type = None;
 
 		while let Some(ref key) = map.next_key::<String>()? {
 			match key.as_ref() {
@@ -79,6 +91,12 @@ impl<'a> Visitor<'a> for EventParamVisitor {
 					}
 					kind = Some(map.next_value()?);
 				}
+				"internalType" => {
+					if internal_type.is_some() {
+						return Err(Error::duplicate_field("internalType"));
+					}
+					internal_type = Some(map.next_value()?);
+				}
 				"components" => {
 					if components.is_some() {
 						return Err(Error::duplicate_field("components"));
@@ -97,9 +115,9 @@ impl<'a> Visitor<'a> for EventParamVisitor {
 		}
 		let name = name.ok_or_else(|| Error::missing_field("name"))?;
 		let mut kind = kind.ok_or_else(|| Error::missing_field("kind"))?;
-		crate::param::set_tuple_components(&mut kind, components)?;
+		let components = crate::param::set_tuple_components(&mut kind, components)?;
 		let indexed = indexed.unwrap_or(false);
-		Ok(EventParam { name, kind, indexed })
+		Ok(EventParam { name, kind, indexed, internal_type, components })
 	}
 }
 
@@ -110,13 +128,13 @@ impl Serialize for EventParam {
 		S: Serializer,
 	{
 		let mut map = serializer.serialize_map(None)?;
+		if let Some(ref internal_type) = self.internal_type {
+			map.serialize_entry("internalType", internal_type)?;
+		}
 		map.serialize_entry("name", &self.name)?;
 		map.serialize_entry("type", &Writer::write_for_abi(&self.kind, false))?;
 		map.serialize_entry("indexed", &self.indexed)?;
-		if let Some(inner_tuple) = crate::param::inner_tuple(&self.kind) {
-			map.serialize_key("components")?;
-			map.serialize_value(&crate::param::SerializeableParamVec(inner_tuple))?;
-		}
+		crate::param::serialize_components(&mut map, &self.kind, &self.components)?;
 		map.end()
 	}
 }
@@ -125,7 +143,10 @@ impl Serialize for EventParam {
 mod tests {
 	#[cfg(not(feature = "std"))]
 	use crate::no_std_prelude::*;
-	use crate::{tests::assert_json_eq, EventParam, ParamType};
+	use crate::{
+		tests::{assert_json_eq, component},
+		EventParam, ParamType,
+	};
 
 	#[test]
 	fn event_param_deserialization() {
@@ -137,7 +158,16 @@ mod tests {
 
 		let deserialized: EventParam = serde_json::from_str(s).unwrap();
 
-		assert_eq!(deserialized, EventParam { name: "foo".to_owned(), kind: ParamType::Address, indexed: true });
+		assert_eq!(
+			deserialized,
+			EventParam {
+				name: "foo".to_owned(),
+				kind: ParamType::Address,
+				indexed: true,
+				internal_type: None,
+				components: vec![]
+			}
+		);
 
 		assert_json_eq(s, serde_json::to_string(&deserialized).unwrap().as_str());
 	}
@@ -171,6 +201,11 @@ mod tests {
 				name: "foo".to_owned(),
 				kind: ParamType::Tuple(vec![ParamType::Uint(48), ParamType::Tuple(vec![ParamType::Address])]),
 				indexed: true,
+				internal_type: None,
+				components: vec![
+					component(ParamType::Uint(48), vec![]),
+					component(ParamType::Tuple(vec![ParamType::Address]), vec![component(ParamType::Address, vec![])]),
+				],
 			}
 		);
 
@@ -237,6 +272,44 @@ mod tests {
 					ParamType::Uint(256),
 				]),
 				indexed: false,
+				internal_type: None,
+				components: vec![
+					component(ParamType::Uint(256), vec![]),
+					component(ParamType::Address, vec![]),
+					component(
+						ParamType::Tuple(vec![ParamType::Address, ParamType::Address]),
+						vec![component(ParamType::Address, vec![]), component(ParamType::Address, vec![])],
+					),
+					component(ParamType::Uint(256), vec![]),
+					component(
+						ParamType::Array(Box::new(ParamType::Tuple(vec![
+							ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::Address, ParamType::Bytes]))),
+							ParamType::Array(Box::new(ParamType::Tuple(vec![
+								ParamType::Address,
+								ParamType::Uint(256)
+							]))),
+							ParamType::Uint(256),
+						]))),
+						vec![
+							component(
+								ParamType::Array(Box::new(ParamType::Tuple(vec![
+									ParamType::Address,
+									ParamType::Bytes
+								]))),
+								vec![component(ParamType::Address, vec![]), component(ParamType::Bytes, vec![])],
+							),
+							component(
+								ParamType::Array(Box::new(ParamType::Tuple(vec![
+									ParamType::Address,
+									ParamType::Uint(256)
+								]))),
+								vec![component(ParamType::Address, vec![]), component(ParamType::Uint(256), vec![])],
+							),
+							component(ParamType::Uint(256), vec![]),
+						],
+					),
+					component(ParamType::Uint(256), vec![]),
+				],
 			}
 		);
 
@@ -14,7 +14,9 @@ use serde::{Deserialize, Serialize};
 #[cfg(not(feature = "std"))]
 use crate::no_std_prelude::*;
 use crate::{
-	decode, encode, signature::short_signature, Bytes, Error, Param, ParamType, Result, StateMutability, Token,
+	decode, encode,
+	signature::{canonical_signature, short_signature},
+	Bytes, Error, NamedToken, Param, ParamType, Result, StateMutability, Token,
 };
 
 /// Contract function specification.
@@ -78,6 +80,16 @@ impl Function {
 		decode(&self.input_param_types(), data)
 	}
 
+	/// Parses the ABI function output, keeping the names of outputs and their tuple components.
+	pub fn decode_output_named(&self, data: &[u8]) -> Result<NamedToken> {
+		Ok(NamedToken::params(&self.outputs, self.decode_output(data)?))
+	}
+
+	/// Parses the ABI function input, keeping the names of inputs and their tuple components.
+	pub fn decode_input_named(&self, data: &[u8]) -> Result<NamedToken> {
+		Ok(NamedToken::params(&self.inputs, self.decode_input(data)?))
+	}
+
 	/// Returns a signature that uniquely identifies this function.
 	///
 	/// Examples:
@@ -85,6 +97,8 @@ impl Function {
 	/// - `functionName():(uint256)`
 	/// - `functionName(bool):(uint256,string)`
 	/// - `functionName(uint256,bytes32):(string,uint256)`
+	///
+	/// See [`Function::canonical_signature`] for the form the selector is hashed from.
 	pub fn signature(&self) -> String {
 		let inputs = self.inputs.iter().map(|p| p.kind.to_string()).collect::<Vec<_>>().join(",");
 
@@ -95,6 +109,11 @@ impl Function {
 			(_, _) => format!("{}({inputs}):({outputs})", self.name),
 		}
 	}
+
+	/// Returns the signature the selector is hashed from, e.g. `transfer(address,uint256)`.
+	pub fn canonical_signature(&self) -> String {
+		canonical_signature(&self.name, &self.input_param_types())
+	}
 }
 
 #[cfg(test)]
@@ -103,7 +122,7 @@ mod tests {
 
 	#[cfg(not(feature = "std"))]
 	use crate::no_std_prelude::*;
-	use crate::{Function, Param, ParamType, StateMutability, Token, Uint};
+	use crate::{encode, Function, NamedToken, Param, ParamType, StateMutability, Token, TupleParam, Uint};
 
 	#[test]
 	fn test_function_encode_call() {
@@ -111,8 +130,8 @@ mod tests {
 		let func = Function {
 			name: "baz".to_owned(),
 			inputs: vec![
-				Param { name: "a".to_owned(), kind: ParamType::Uint(32), internal_type: None },
-				Param { name: "b".to_owned(), kind: ParamType::Bool, internal_type: None },
+				Param { name: "a".to_owned(), kind: ParamType::Uint(32), internal_type: None, components: vec![] },
+				Param { name: "b".to_owned(), kind: ParamType::Bool, internal_type: None, components: vec![] },
 			],
 			outputs: vec![],
 			constant: None,
@@ -127,5 +146,41 @@ mod tests {
 
 		let expected_sig = hex!("cdcd77c0").to_vec();
 		assert_eq!(func.short_signature().to_vec(), expected_sig);
+		assert_eq!(func.canonical_signature(), "baz(uint32,bool)");
+	}
+
+	#[test]
+	fn test_function_decode_output_named() {
+		let component = |name: &str, kind| TupleParam {
+			name: Some(name.to_owned()),
+			kind,
+			internal_type: None,
+			components: vec![],
+		};
+		#[allow(deprecated)]
+		let func = Function {
+			name: "order".to_owned(),
+			inputs: vec![],
+			outputs: vec![
+				Param {
+					name: "order".to_owned(),
+					kind: ParamType::Tuple(vec![ParamType::Address, ParamType::Uint(256)]),
+					internal_type: Some("struct Order".to_owned()),
+					components: vec![component("maker", ParamType::Address), component("amount", ParamType::Uint(256))],
+				},
+				Param { name: "".to_owned(), kind: ParamType::Bool, internal_type: None, components: vec![] },
+			],
+			constant: None,
+			state_mutability: StateMutability::View,
+		};
+
+		let tokens =
+			[Token::Tuple(vec![Token::Address([0x11; 20].into()), Token::Uint(Uint::from(1000))]), Token::Bool(true)];
+		let named = func.decode_output_named(&encode(&tokens)).unwrap();
+
+		let order = named.get("order").unwrap();
+		assert_eq!(order.get("maker").and_then(NamedToken::as_token), Some(&Token::Address([0x11; 20].into())));
+		assert_eq!(order.get("amount").and_then(NamedToken::as_token), Some(&Token::Uint(Uint::from(1000))));
+		assert_eq!(named.get("1").and_then(NamedToken::as_token), Some(&Token::Bool(true)));
 	}
 }
@@ -0,0 +1,176 @@
+// Copyright 2015-2020 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Solidity types params are declared with.
+
+use core::fmt;
+
+#[cfg(not(feature = "std"))]
+use crate::no_std_prelude::*;
+
+/// Solidity type a param is declared with, as reported by solc in `internalType`.
+///
+/// Array suffixes are dropped, the param type already tells the shape of the array.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InternalType {
+	/// Elementary type, e.g. `uint256` or `address payable`.
+	Elementary(String),
+	/// Struct, e.g. `struct Exchange.Order`.
+	Struct {
+		/// Contract the struct is defined in, `None` for structs defined at file level.
+		contract: Option<String>,
+		/// Struct name.
+		name: String,
+	},
+	/// Enum, e.g. `enum Exchange.Side`.
+	Enum {
+		/// Contract the enum is defined in, `None` for enums defined at file level.
+		contract: Option<String>,
+		/// Enum name.
+		name: String,
+	},
+	/// Contract or interface, e.g. `contract IERC20`.
+	Contract(String),
+	/// User defined value type, e.g. `Exchange.Price` for `type Price is uint256`.
+	UserDefined {
+		/// Contract the type is defined in, `None` for types defined at file level.
+		contract: Option<String>,
+		/// Type name.
+		name: String,
+	},
+}
+
+impl InternalType {
+	/// Parses `internalType`, e.g. `struct Exchange.Order[]`.
+	pub fn parse(internal_type: &str) -> Self {
+		let mut base = internal_type.trim();
+		while let Some(stripped) = base.strip_suffix(']').and_then(|rest| rest.rfind('[').map(|pos| &rest[..pos])) {
+			base = stripped;
+		}
+
+		if let Some(path) = base.strip_prefix("struct ") {
+			let (contract, name) = split_path(path);
+			InternalType::Struct { contract, name }
+		} else if let Some(path) = base.strip_prefix("enum ") {
+			let (contract, name) = split_path(path);
+			InternalType::Enum { contract, name }
+		} else if let Some(name) = base.strip_prefix("contract ") {
+			InternalType::Contract(name.to_owned())
+		} else if is_elementary(base) {
+			InternalType::Elementary(base.to_owned())
+		} else {
+			let (contract, name) = split_path(base);
+			InternalType::UserDefined { contract, name }
+		}
+	}
+
+	/// Name of the struct, enum, contract or user defined type, qualified with the contract it is defined in,
+	/// e.g. `Exchange.Order`. `None` for elementary types.
+	pub fn qualified_name(&self) -> Option<String> {
+		match self {
+			InternalType::Elementary(_) => None,
+			InternalType::Contract(name) => Some(name.clone()),
+			InternalType::Struct { contract, name }
+			| InternalType::Enum { contract, name }
+			| InternalType::UserDefined { contract, name } => match contract {
+				Some(contract) => Some(format!("{contract}.{name}")),
+				None => Some(name.clone()),
+			},
+		}
+	}
+}
+
+impl fmt::Display for InternalType {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let name = self.qualified_name().unwrap_or_default();
+		match self {
+			InternalType::Elementary(elementary) => f.write_str(elementary),
+			InternalType::Struct { .. } => write!(f, "struct {name}"),
+			InternalType::Enum { .. } => write!(f, "enum {name}"),
+			InternalType::Contract(_) => write!(f, "contract {name}"),
+			InternalType::UserDefined { .. } => f.write_str(&name),
+		}
+	}
+}
+
+fn split_path(path: &str) -> (Option<String>, String) {
+	match path.rsplit_once('.') {
+		Some((contract, name)) => (Some(contract.to_owned()), name.to_owned()),
+		None => (None, path.to_owned()),
+	}
+}
+
+fn is_elementary(name: &str) -> bool {
+	let sized = |prefix: &str| matches!(name.strip_prefix(prefix), Some(size) if size.chars().all(|c| c.is_ascii_digit() || c == 'x'));
+	matches!(name, "address" | "address payable" | "bool" | "string" | "function")
+		|| name.starts_with("function ")
+		|| ["uint", "int", "bytes", "ufixed", "fixed"].iter().any(|prefix| sized(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+	#[cfg(not(feature = "std"))]
+	use crate::no_std_prelude::*;
+
+	use super::InternalType;
+	use crate::{EventParam, Param, ParamType, TupleParam};
+
+	#[test]
+	fn parse() {
+		assert_eq!(
+			InternalType::parse("struct Exchange.Order[][2]"),
+			InternalType::Struct { contract: Some("Exchange".into()), name: "Order".into() }
+		);
+		assert_eq!(InternalType::parse("struct Order"), InternalType::Struct { contract: None, name: "Order".into() });
+		assert_eq!(
+			InternalType::parse("enum Exchange.Side"),
+			InternalType::Enum { contract: Some("Exchange".into()), name: "Side".into() }
+		);
+		assert_eq!(InternalType::parse("contract IERC20"), InternalType::Contract("IERC20".into()));
+		assert_eq!(
+			InternalType::parse("Exchange.Price"),
+			InternalType::UserDefined { contract: Some("Exchange".into()), name: "Price".into() }
+		);
+		assert_eq!(InternalType::parse("Price"), InternalType::UserDefined { contract: None, name: "Price".into() });
+		assert_eq!(InternalType::parse("uint256[]"), InternalType::Elementary("uint256".into()));
+		assert_eq!(InternalType::parse("address payable"), InternalType::Elementary("address payable".into()));
+		assert_eq!(InternalType::parse("bytes"), InternalType::Elementary("bytes".into()));
+	}
+
+	#[test]
+	fn params() {
+		let order = Some(InternalType::Struct { contract: Some("Exchange".into()), name: "Order".into() });
+		let kind = ParamType::Tuple(vec![]);
+		let internal_type = Some("struct Exchange.Order".to_owned());
+
+		let param =
+			Param { name: "a".into(), kind: kind.clone(), internal_type: internal_type.clone(), components: vec![] };
+		assert_eq!(param.internal_type(), order);
+		let param = EventParam {
+			name: "a".into(),
+			kind: kind.clone(),
+			indexed: false,
+			internal_type: internal_type.clone(),
+			components: vec![],
+		};
+		assert_eq!(param.internal_type(), order);
+		let param = TupleParam { name: None, kind, internal_type, components: vec![] };
+		assert_eq!(param.internal_type(), order);
+		let param = TupleParam { name: None, kind: ParamType::Bool, internal_type: None, components: vec![] };
+		assert_eq!(param.internal_type(), None);
+	}
+
+	#[test]
+	fn display() {
+		for internal_type in ["struct Exchange.Order", "enum Side", "contract IERC20", "Exchange.Price", "uint8"] {
+			assert_eq!(InternalType::parse(internal_type).to_string(), internal_type);
+		}
+		assert_eq!(InternalType::parse("struct Exchange.Order").qualified_name().as_deref(), Some("Exchange.Order"));
+		assert_eq!(InternalType::parse("uint8").qualified_name(), None);
+	}
+}
@@ -30,6 +30,9 @@ mod no_std_prelude {
 #[cfg(not(feature = "std"))]
 use no_std_prelude::*;
 
+#[cfg(feature = "full-serde")]
+mod artifact;
+mod checksum;
 mod constructor;
 mod contract;
 mod decoder;
@@ -40,15 +43,21 @@ mod event;
 mod event_param;
 mod filter;
 mod function;
+mod internal_type;
+mod lint;
 mod log;
+mod named_token;
+#[cfg(feature = "serde")]
+pub mod natspec;
 #[cfg(feature = "serde")]
 mod operation;
 mod param;
 pub mod param_type;
 mod signature;
+#[cfg(feature = "full-serde")]
+mod standard_json;
 mod state_mutability;
 pub mod token;
-#[cfg(feature = "serde")]
 mod tuple_param;
 mod util;
 
@@ -57,9 +66,10 @@ mod tests;
 
 pub use ethereum_types;
 
-#[cfg(feature = "serde")]
-pub use crate::tuple_param::TupleParam;
+#[cfg(feature = "full-serde")]
+pub use crate::{artifact::Artifact, standard_json::StandardJsonOutput};
 pub use crate::{
+	checksum::checksum_address,
 	constructor::Constructor,
 	contract::{Contract, Events, Functions},
 	decoder::{decode, decode_validate},
@@ -70,12 +80,16 @@ pub use crate::{
 	event_param::EventParam,
 	filter::{RawTopicFilter, Topic, TopicFilter},
 	function::Function,
+	internal_type::InternalType,
+	lint::Lint,
 	log::{Log, LogFilter, LogParam, ParseLog, RawLog},
+	named_token::NamedToken,
 	param::Param,
 	param_type::ParamType,
-	signature::{long_signature, short_signature},
+	signature::{canonical_signature, long_signature, short_signature},
 	state_mutability::StateMutability,
 	token::Token,
+	tuple_param::TupleParam,
 };
 
 /// ABI word.
@@ -0,0 +1,278 @@
+// Copyright 2015-2020 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Selector collision and shadowing checks.
+
+use core::fmt;
+
+#[cfg(not(feature = "std"))]
+use crate::no_std_prelude::*;
+use crate::{Contract, Function, Hash, ParamType};
+
+/// Potential problem found while linting a contract ABI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Lint {
+	/// Two functions share the same 4-byte selector.
+	FunctionSelectorCollision {
+		/// Shared selector.
+		selector: [u8; 4],
+		/// Signature of the first function.
+		first: String,
+		/// Signature of the second function.
+		second: String,
+	},
+	/// A function and a custom error share the same 4-byte selector.
+	ErrorSelectorCollision {
+		/// Shared selector.
+		selector: [u8; 4],
+		/// Signature of the function.
+		function: String,
+		/// Signature of the error.
+		error: String,
+	},
+	/// Two overloads of a function accept the same input when it is tokenized leniently,
+	/// so a string-based caller cannot tell which one is meant.
+	AmbiguousOverload {
+		/// Signature of the first overload.
+		first: String,
+		/// Signature of the second overload.
+		second: String,
+	},
+	/// Two events share the same topic0.
+	EventTopicCollision {
+		/// Shared topic0.
+		topic: Hash,
+		/// Signature of the first event.
+		first: String,
+		/// Signature of the second event.
+		second: String,
+	},
+}
+
+impl fmt::Display for Lint {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			Lint::FunctionSelectorCollision { ref selector, ref first, ref second } => {
+				write!(f, "functions `{first}` and `{second}` share selector 0x{}", hex::encode(selector))
+			}
+			Lint::ErrorSelectorCollision { ref selector, ref function, ref error } => {
+				write!(f, "function `{function}` and error `{error}` share selector 0x{}", hex::encode(selector))
+			}
+			Lint::AmbiguousOverload { ref first, ref second } => {
+				write!(f, "overloads `{first}` and `{second}` are ambiguous for lenient tokenization")
+			}
+			Lint::EventTopicCollision { ref topic, ref first, ref second } => {
+				write!(f, "events `{first}` and `{second}` share topic0 {topic:?}")
+			}
+		}
+	}
+}
+
+pub(crate) fn lint(contract: &Contract) -> Vec<Lint> {
+	let functions: Vec<_> = contract.functions().collect();
+	let errors: Vec<_> = contract.errors().collect();
+	let events: Vec<_> = contract.events().filter(|e| !e.anonymous).collect();
+
+	let mut result = vec![];
+
+	for (i, first) in functions.iter().enumerate() {
+		for second in &functions[i + 1..] {
+			let selector = first.short_signature();
+			if selector == second.short_signature() {
+				result.push(Lint::FunctionSelectorCollision {
+					selector,
+					first: first.canonical_signature(),
+					second: second.canonical_signature(),
+				});
+			} else if first.name == second.name && is_ambiguous_overload(first, second) {
+				result.push(Lint::AmbiguousOverload {
+					first: first.canonical_signature(),
+					second: second.canonical_signature(),
+				});
+			}
+		}
+
+		for error in &errors {
+			let selector = first.short_signature();
+			if selector[..] == error.signature()[..4] {
+				result.push(Lint::ErrorSelectorCollision {
+					selector,
+					function: first.canonical_signature(),
+					error: error.canonical_signature(),
+				});
+			}
+		}
+	}
+
+	for (i, first) in events.iter().enumerate() {
+		for second in &events[i + 1..] {
+			let topic = first.signature();
+			if topic == second.signature() {
+				result.push(Lint::EventTopicCollision {
+					topic,
+					first: first.canonical_signature(),
+					second: second.canonical_signature(),
+				});
+			}
+		}
+	}
+
+	result
+}
+
+fn is_ambiguous_overload(first: &Function, second: &Function) -> bool {
+	first.inputs.len() == second.inputs.len()
+		&& first.inputs.iter().zip(second.inputs.iter()).all(|(a, b)| lenient_overlap(&a.kind, &b.kind))
+}
+
+/// Returns whether some string value is accepted by `LenientTokenizer` for both param types.
+fn lenient_overlap(first: &ParamType, second: &ParamType) -> bool {
+	use ParamType::*;
+
+	match (first, second) {
+		// strings are taken verbatim, so they accept anything
+		(String, _) | (_, String) => true,
+		// decimal numbers, `1`/`0` booleans and hex words are shared by all of these
+		(Uint(_) | Int(_), Uint(_) | Int(_) | Bool | Bytes | FixedBytes(_) | Address) => true,
+		(Bool | Bytes | FixedBytes(_) | Address, Uint(_) | Int(_)) => true,
+		(Bool, Bool) => true,
+		(Bytes, Bytes | FixedBytes(_) | Address) | (FixedBytes(_) | Address, Bytes) => true,
+		(Address, Address) => true,
+		(Address, FixedBytes(len)) | (FixedBytes(len), Address) => *len == 20,
+		(FixedBytes(a), FixedBytes(b)) => a == b,
+		// `[]` is a valid value for every dynamic array
+		(Array(_), Array(_)) => true,
+		(Array(a), FixedArray(b, len)) | (FixedArray(b, len), Array(a)) => *len == 0 || lenient_overlap(a, b),
+		(FixedArray(a, a_len), FixedArray(b, b_len)) => a_len == b_len && (*a_len == 0 || lenient_overlap(a, b)),
+		(Tuple(a), Tuple(b)) => a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| lenient_overlap(a, b)),
+		_ => false,
+	}
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+	use hex_literal::hex;
+
+	#[cfg(not(feature = "std"))]
+	use crate::no_std_prelude::*;
+	use crate::{Contract, Lint};
+
+	#[test]
+	fn clean_contract() {
+		let contract: Contract = serde_json::from_str(
+			r#"[
+				{ "type": "function", "name": "foo", "inputs": [{ "name": "a", "type": "address" }], "outputs": [] },
+				{ "type": "function", "name": "foo", "inputs": [{ "name": "a", "type": "bool" }], "outputs": [] },
+				{ "type": "error", "name": "Bar", "inputs": [] },
+				{ "type": "event", "name": "Baz", "inputs": [], "anonymous": false }
+			]"#,
+		)
+		.unwrap();
+
+		assert_eq!(contract.lint(), vec![]);
+	}
+
+	#[test]
+	fn function_selector_collision() {
+		let contract: Contract = serde_json::from_str(
+			r#"[
+				{ "type": "function", "name": "burn", "inputs": [{ "name": "a", "type": "uint256" }], "outputs": [] },
+				{
+					"type": "function",
+					"name": "collate_propagate_storage",
+					"inputs": [{ "name": "a", "type": "bytes16" }],
+					"outputs": []
+				}
+			]"#,
+		)
+		.unwrap();
+
+		assert_eq!(
+			contract.lint(),
+			vec![Lint::FunctionSelectorCollision {
+				selector: hex!("42966c68"),
+				first: "burn(uint256)".into(),
+				second: "collate_propagate_storage(bytes16)".into(),
+			}]
+		);
+	}
+
+	#[test]
+	fn error_selector_collision() {
+		let contract: Contract = serde_json::from_str(
+			r#"[
+				{ "type": "function", "name": "burn", "inputs": [{ "name": "a", "type": "uint256" }], "outputs": [] },
+				{ "type": "error", "name": "burn", "inputs": [{ "name": "amount", "type": "uint256" }] }
+			]"#,
+		)
+		.unwrap();
+
+		assert_eq!(
+			contract.lint(),
+			vec![Lint::ErrorSelectorCollision {
+				selector: hex!("42966c68"),
+				function: "burn(uint256)".into(),
+				error: "burn(uint256)".into(),
+			}]
+		);
+	}
+
+	#[test]
+	fn ambiguous_overloads() {
+		let contract: Contract = serde_json::from_str(
+			r#"[
+				{ "type": "function", "name": "foo", "inputs": [{ "name": "a", "type": "uint8" }], "outputs": [] },
+				{ "type": "function", "name": "foo", "inputs": [{ "name": "a", "type": "int256" }], "outputs": [] },
+				{ "type": "function", "name": "foo", "inputs": [{ "name": "a", "type": "bytes4" }], "outputs": [] },
+				{ "type": "function", "name": "bar", "inputs": [{ "name": "a", "type": "address" }], "outputs": [] },
+				{ "type": "function", "name": "bar", "inputs": [{ "name": "a", "type": "bytes32" }], "outputs": [] }
+			]"#,
+		)
+		.unwrap();
+
+		assert_eq!(
+			contract.lint(),
+			vec![
+				Lint::AmbiguousOverload { first: "foo(uint8)".into(), second: "foo(int256)".into() },
+				Lint::AmbiguousOverload { first: "foo(uint8)".into(), second: "foo(bytes4)".into() },
+				Lint::AmbiguousOverload { first: "foo(int256)".into(), second: "foo(bytes4)".into() },
+			]
+		);
+	}
+
+	#[test]
+	fn event_topic_collision() {
+		let contract: Contract = serde_json::from_str(
+			r#"[
+				{
+					"type": "event",
+					"name": "Transfer",
+					"inputs": [{ "name": "from", "type": "address", "indexed": true }],
+					"anonymous": false
+				},
+				{
+					"type": "event",
+					"name": "Transfer",
+					"inputs": [{ "name": "from", "type": "address", "indexed": false }],
+					"anonymous": false
+				},
+				{
+					"type": "event",
+					"name": "Transfer",
+					"inputs": [{ "name": "from", "type": "address", "indexed": false }],
+					"anonymous": true
+				}
+			]"#,
+		)
+		.unwrap();
+
+		let lints = contract.lint();
+		assert_eq!(lints.len(), 1);
+		assert!(matches!(lints[0], Lint::EventTopicCollision { ref first, .. } if first == "Transfer(address)"));
+	}
+}
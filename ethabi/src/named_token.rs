@@ -0,0 +1,213 @@
+// Copyright 2015-2020 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Decoded values which keep param names.
+
+#[cfg(feature = "serde")]
+use serde::{
+	ser::{SerializeMap, SerializeSeq},
+	Serialize, Serializer,
+};
+
+#[cfg(not(feature = "std"))]
+use crate::no_std_prelude::*;
+#[cfg(feature = "serde")]
+use crate::{checksum_address, util::signed_decimal};
+use crate::{Param, Token, TupleParam};
+
+/// Decoded value which keeps the names of params and tuple components all the way down.
+///
+/// With the `serde` feature it serializes to json objects keyed by name, e.g.
+/// `{"order": {"maker": "0x..", "amount": "1000"}}`. Numbers are decimal strings, addresses checksummed
+/// and bytes `0x` prefixed hex.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NamedToken {
+	/// Value of an elementary type.
+	Value(Token),
+	/// Elements of an array or fixed array, by index.
+	Array(Vec<NamedToken>),
+	/// Tuple components or params, by name. Unnamed ones are named by their position.
+	Tuple(Vec<(String, NamedToken)>),
+}
+
+impl NamedToken {
+	/// Names the components of `token` after `components`, the components of the tuple it is or contains.
+	pub fn new(token: Token, components: &[TupleParam]) -> Self {
+		match token {
+			Token::Array(tokens) | Token::FixedArray(tokens) => {
+				NamedToken::Array(tokens.into_iter().map(|token| NamedToken::new(token, components)).collect())
+			}
+			Token::Tuple(tokens) => NamedToken::tuple(
+				components
+					.iter()
+					.map(|component| (component.name.as_deref().unwrap_or_default(), &component.components[..])),
+				tokens,
+			),
+			token => NamedToken::Value(token),
+		}
+	}
+
+	/// Names `tokens` after `params`.
+	pub(crate) fn params(params: &[Param], tokens: Vec<Token>) -> Self {
+		NamedToken::tuple(params.iter().map(|param| (param.name.as_str(), &param.components[..])), tokens)
+	}
+
+	/// Names `tokens` after `params`, given as names and tuple components.
+	pub(crate) fn tuple<'a, I>(params: I, tokens: Vec<Token>) -> Self
+	where
+		I: IntoIterator<Item = (&'a str, &'a [TupleParam])>,
+	{
+		let mut params = params.into_iter();
+		let named = tokens
+			.into_iter()
+			.enumerate()
+			.map(|(index, token)| {
+				let (name, components) = params.next().unwrap_or_default();
+				let name = if name.is_empty() { index.to_string() } else { name.to_owned() };
+				(name, NamedToken::new(token, components))
+			})
+			.collect();
+		NamedToken::Tuple(named)
+	}
+
+	/// Returns the tuple component or param with given name.
+	pub fn get(&self, name: &str) -> Option<&NamedToken> {
+		match self {
+			NamedToken::Tuple(named) => named.iter().find(|(n, _)| n == name).map(|(_, token)| token),
+			_ => None,
+		}
+	}
+
+	/// Returns the value of an elementary type.
+	pub fn as_token(&self) -> Option<&Token> {
+		match self {
+			NamedToken::Value(token) => Some(token),
+			_ => None,
+		}
+	}
+
+	/// Returns array elements.
+	pub fn as_array(&self) -> Option<&[NamedToken]> {
+		match self {
+			NamedToken::Array(elements) => Some(elements),
+			_ => None,
+		}
+	}
+
+	/// Converts back to a token, dropping names.
+	pub fn into_token(self) -> Token {
+		match self {
+			NamedToken::Value(token) => token,
+			NamedToken::Array(elements) => Token::Array(elements.into_iter().map(NamedToken::into_token).collect()),
+			NamedToken::Tuple(named) => Token::Tuple(named.into_iter().map(|(_, token)| token.into_token()).collect()),
+		}
+	}
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for NamedToken {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		match self {
+			NamedToken::Value(token) => match token {
+				Token::Bool(value) => serializer.serialize_bool(*value),
+				Token::String(value) => serializer.serialize_str(value),
+				Token::Address(address) => serializer.serialize_str(&checksum_address(address, None)),
+				Token::Bytes(bytes) | Token::FixedBytes(bytes) => {
+					serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+				}
+				Token::Uint(value) => serializer.serialize_str(&value.to_string()),
+				Token::Int(value) => serializer.serialize_str(&signed_decimal(value)),
+				// composite tokens are only left without components to name them by
+				token => NamedToken::new(token.clone(), &[]).serialize(serializer),
+			},
+			NamedToken::Array(elements) => {
+				let mut seq = serializer.serialize_seq(Some(elements.len()))?;
+				for element in elements {
+					seq.serialize_element(element)?;
+				}
+				seq.end()
+			}
+			NamedToken::Tuple(named) => {
+				let mut map = serializer.serialize_map(Some(named.len()))?;
+				for (name, token) in named {
+					map.serialize_entry(name, token)?;
+				}
+				map.end()
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[cfg(not(feature = "std"))]
+	use crate::no_std_prelude::*;
+	use crate::{Address, NamedToken, ParamType, Token, TupleParam, Uint};
+
+	fn order() -> TupleParam {
+		let component = |name: &str, kind| TupleParam {
+			name: Some(name.to_owned()),
+			kind,
+			internal_type: None,
+			components: vec![],
+		};
+		TupleParam {
+			name: Some("order".to_owned()),
+			kind: ParamType::Tuple(vec![ParamType::Address, ParamType::Uint(256)]),
+			internal_type: Some("struct Exchange.Order".to_owned()),
+			components: vec![component("maker", ParamType::Address), component("amount", ParamType::Uint(256))],
+		}
+	}
+
+	#[test]
+	fn names_nested_components() {
+		let order = order();
+		let token = Token::Array(vec![Token::Tuple(vec![
+			Token::Address(Address::from_low_u64_be(1)),
+			Token::Uint(Uint::from(1000)),
+		])]);
+
+		let named = NamedToken::new(token.clone(), &order.components);
+
+		let first = &named.as_array().unwrap()[0];
+		assert_eq!(first.get("amount").and_then(NamedToken::as_token), Some(&Token::Uint(Uint::from(1000))));
+		assert_eq!(named.into_token(), token);
+	}
+
+	#[test]
+	fn names_unnamed_by_position() {
+		let named = NamedToken::tuple([("", &[][..])], vec![Token::Bool(true), Token::Bool(false)]);
+		assert_eq!(named.get("0"), Some(&NamedToken::Value(Token::Bool(true))));
+		assert_eq!(named.get("1"), Some(&NamedToken::Value(Token::Bool(false))));
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serialize() {
+		let order = order();
+		let named = NamedToken::tuple(
+			[("order", &order.components[..]), ("delta", &[][..]), ("data", &[][..])],
+			vec![
+				Token::Tuple(vec![
+					Token::Address("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".parse().unwrap()),
+					Token::Uint(Uint::from(1000)),
+				]),
+				Token::Int(Uint::MAX - 41),
+				Token::Bytes(vec![0xca, 0xfe]),
+			],
+		);
+
+		assert_eq!(
+			serde_json::to_string(&named).unwrap(),
+			r#"{"order":{"maker":"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed","amount":"1000"},"delta":"-42","data":"0xcafe"}"#
+		);
+	}
+}
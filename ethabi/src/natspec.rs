@@ -0,0 +1,128 @@
+// Copyright 2015-2020 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! NatSpec documentation emitted by solc as `userdoc` and `devdoc`.
+
+use alloc::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(feature = "std"))]
+use crate::no_std_prelude::*;
+
+/// End user documentation: `@notice` tags.
+///
+/// Functions, events and errors are keyed by signature, e.g. `transfer(address,uint256)`,
+/// the constructor by `constructor`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserDoc {
+	/// Contract notice.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub notice: Option<String>,
+	/// Function and constructor notices.
+	#[serde(default)]
+	pub methods: BTreeMap<String, UserDocEntry>,
+	/// Event notices.
+	#[serde(default)]
+	pub events: BTreeMap<String, UserDocEntry>,
+	/// Error notices, one for each error definition with the same signature.
+	#[serde(default)]
+	pub errors: BTreeMap<String, Vec<UserDocEntry>>,
+}
+
+/// End user documentation of a function, event or error.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserDocEntry {
+	/// `@notice` text.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub notice: Option<String>,
+}
+
+/// Developer documentation: `@title`, `@author`, `@dev`, `@param` and `@return` tags.
+///
+/// Keyed the same way as [`UserDoc`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DevDoc {
+	/// Contract title.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub title: Option<String>,
+	/// Contract author.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub author: Option<String>,
+	/// Contract details.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub details: Option<String>,
+	/// Function and constructor documentation.
+	#[serde(default)]
+	pub methods: BTreeMap<String, DevDocEntry>,
+	/// Event documentation.
+	#[serde(default)]
+	pub events: BTreeMap<String, DevDocEntry>,
+	/// Error documentation, one for each error definition with the same signature.
+	#[serde(default)]
+	pub errors: BTreeMap<String, Vec<DevDocEntry>>,
+}
+
+/// Developer documentation of a function, event or error.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DevDocEntry {
+	/// `@dev` text.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub details: Option<String>,
+	/// `@param` texts by param name.
+	#[serde(default)]
+	pub params: BTreeMap<String, String>,
+	/// `@return` texts by output name, or `_0`, `_1`, ... for unnamed outputs.
+	#[serde(default)]
+	pub returns: BTreeMap<String, String>,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{DevDoc, UserDoc};
+
+	#[test]
+	fn deserialize() {
+		let userdoc: UserDoc = serde_json::from_str(
+			r#"{
+				"kind": "user",
+				"version": 1,
+				"notice": "Keeps balances.",
+				"methods": { "transfer(address,uint256)": { "notice": "Moves tokens." } },
+				"events": {},
+				"errors": { "Insufficient(uint256)": [{ "notice": "Balance too low." }] }
+			}"#,
+		)
+		.unwrap();
+		assert_eq!(userdoc.notice.as_deref(), Some("Keeps balances."));
+		assert_eq!(userdoc.methods["transfer(address,uint256)"].notice.as_deref(), Some("Moves tokens."));
+		assert_eq!(userdoc.errors["Insufficient(uint256)"][0].notice.as_deref(), Some("Balance too low."));
+
+		let devdoc: DevDoc = serde_json::from_str(
+			r#"{
+				"kind": "dev",
+				"version": 1,
+				"title": "Token",
+				"methods": {
+					"transfer(address,uint256)": {
+						"details": "Reverts on low balance.",
+						"params": { "to": "Recipient." },
+						"returns": { "_0": "Always true." }
+					}
+				},
+				"stateVariables": {}
+			}"#,
+		)
+		.unwrap();
+		let transfer = &devdoc.methods["transfer(address,uint256)"];
+		assert_eq!(devdoc.title.as_deref(), Some("Token"));
+		assert_eq!(transfer.details.as_deref(), Some("Reverts on low balance."));
+		assert_eq!(transfer.params["to"], "Recipient.");
+		assert_eq!(transfer.returns["_0"], "Always true.");
+	}
+}
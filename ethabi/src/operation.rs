@@ -40,7 +40,7 @@ mod tests {
 	use super::Operation;
 	#[cfg(not(feature = "std"))]
 	use crate::no_std_prelude::*;
-	use crate::{tests::assert_ser_de, Event, EventParam, Function, Param, ParamType, StateMutability};
+	use crate::{tests::assert_ser_de, Event, EventParam, Function, Param, ParamType, StateMutability, TupleParam};
 
 	#[test]
 	fn operation() {
@@ -59,7 +59,12 @@ mod tests {
 		#[allow(deprecated)]
 		let function = Function {
 			name: "foo".to_owned(),
-			inputs: vec![Param { name: "a".to_owned(), kind: ParamType::Address, internal_type: None }],
+			inputs: vec![Param {
+				name: "a".to_owned(),
+				kind: ParamType::Address,
+				internal_type: None,
+				components: vec![],
+			}],
 			outputs: vec![],
 			constant: None,
 			state_mutability: StateMutability::NonPayable,
@@ -115,7 +120,13 @@ mod tests {
 			Operation::Event(Event {
 				name: "E".to_owned(),
 				inputs: vec![
-					EventParam { name: "a".to_owned(), kind: ParamType::Address, indexed: true },
+					EventParam {
+						name: "a".to_owned(),
+						kind: ParamType::Address,
+						indexed: true,
+						internal_type: None,
+						components: vec![]
+					},
 					EventParam {
 						name: "b".to_owned(),
 						kind: ParamType::Array(Box::new(ParamType::Tuple(vec![
@@ -123,7 +134,28 @@ mod tests {
 							ParamType::Uint(256),
 							ParamType::Bytes
 						]))),
-						indexed: false
+						indexed: false,
+						internal_type: Some("struct Action[]".to_owned()),
+						components: vec![
+							TupleParam {
+								name: Some("to".to_owned()),
+								kind: ParamType::Address,
+								internal_type: Some("address".to_owned()),
+								components: vec![],
+							},
+							TupleParam {
+								name: Some("value".to_owned()),
+								kind: ParamType::Uint(256),
+								internal_type: Some("uint256".to_owned()),
+								components: vec![],
+							},
+							TupleParam {
+								name: Some("data".to_owned()),
+								kind: ParamType::Bytes,
+								internal_type: Some("bytes".to_owned()),
+								components: vec![],
+							},
+						]
 					},
 				],
 				anonymous: false,
@@ -20,9 +20,9 @@ use serde::{
 
 #[cfg(not(feature = "std"))]
 use crate::no_std_prelude::*;
-use crate::ParamType;
 #[cfg(feature = "serde")]
-use crate::{param_type::Writer, TupleParam};
+use crate::param_type::Writer;
+use crate::{InternalType, ParamType, TupleParam};
 
 /// Function param.
 #[derive(Debug, Clone, PartialEq)]
@@ -33,6 +33,15 @@ pub struct Param {
 	pub kind: ParamType,
 	/// Additional Internal type.
 	pub internal_type: Option<String>,
+	/// Components of the tuple in `kind`, if it is or contains one, keeping their names and internal types.
+	pub components: Vec<TupleParam>,
+}
+
+impl Param {
+	/// Parses `internal_type`, telling structs, enums, contracts and user defined value types apart.
+	pub fn internal_type(&self) -> Option<InternalType> {
+		self.internal_type.as_deref().map(InternalType::parse)
+	}
 }
 
 #[cfg(feature = "serde")]
@@ -97,8 +106,8 @@ impl<'a> Visitor<'a> for ParamVisitor {
 		}
 		let name = name.ok_or_else(|| Error::missing_field("name"))?;
 		let mut kind = kind.ok_or_else(|| Error::missing_field("kind"))?;
-		set_tuple_components::<V::Error>(&mut kind, components)?;
-		Ok(Param { name, kind, internal_type })
+		let components = set_tuple_components::<V::Error>(&mut kind, components)?;
+		Ok(Param { name, kind, internal_type, components })
 	}
 }
 
@@ -114,10 +123,7 @@ impl Serialize for Param {
 		}
 		map.serialize_entry("name", &self.name)?;
 		map.serialize_entry("type", &Writer::write_for_abi(&self.kind, false))?;
-		if let Some(inner_tuple) = inner_tuple(&self.kind) {
-			map.serialize_key("components")?;
-			map.serialize_value(&SerializeableParamVec(inner_tuple))?;
-		}
+		serialize_components(&mut map, &self.kind, &self.components)?;
 		map.end()
 	}
 }
@@ -146,14 +152,57 @@ pub(crate) fn inner_tuple(mut param: &ParamType) -> Option<&Vec<ParamType>> {
 	}
 }
 
+/// Fills in the tuple in `kind` with the types of `components`, returning the components to keep.
+///
+/// Fails if `components` are given for a type without a tuple, or disagree with a tuple type written out in full,
+/// as in `(uint256,bool)`.
 #[cfg(feature = "serde")]
 pub(crate) fn set_tuple_components<Error: serde::de::Error>(
 	kind: &mut ParamType,
 	components: Option<Vec<TupleParam>>,
-) -> Result<(), Error> {
-	if let Some(inner_tuple_mut) = inner_tuple_mut(kind) {
-		let tuple_params = components.ok_or_else(|| Error::missing_field("components"))?;
-		inner_tuple_mut.extend(tuple_params.into_iter().map(|param| param.kind))
+) -> Result<Vec<TupleParam>, Error> {
+	match inner_tuple_mut(kind) {
+		Some(inner_tuple_mut) => {
+			let tuple_params = components.ok_or_else(|| Error::missing_field("components"))?;
+			let kinds = tuple_params.iter().map(|param| &param.kind);
+			if inner_tuple_mut.is_empty() {
+				inner_tuple_mut.extend(kinds.cloned());
+			} else if !inner_tuple_mut.iter().eq(kinds) {
+				return Err(Error::custom("`components` do not match the tuple in `type`"));
+			}
+			Ok(tuple_params)
+		}
+		None if components.is_some_and(|components| !components.is_empty()) => {
+			Err(Error::custom("`components` are only allowed for tuple types"))
+		}
+		None => Ok(Vec::new()),
+	}
+}
+
+/// Serializes the components of the tuple in `kind`, with their names if `components` are given.
+///
+/// Fails if `components` are given but disagree with `kind`, rather than dropping their names.
+#[cfg(feature = "serde")]
+pub(crate) fn serialize_components<M: SerializeMap>(
+	map: &mut M,
+	kind: &ParamType,
+	components: &[TupleParam],
+) -> Result<(), M::Error> {
+	let inner_tuple = inner_tuple(kind);
+	let matches = match inner_tuple {
+		Some(inner_tuple) => components.is_empty() || inner_tuple.iter().eq(components.iter().map(|c| &c.kind)),
+		None => components.is_empty(),
+	};
+	if !matches {
+		return Err(serde::ser::Error::custom("`components` do not match the tuple in `kind`"));
+	}
+
+	if let Some(inner_tuple) = inner_tuple {
+		map.serialize_key("components")?;
+		match components.is_empty() {
+			true => map.serialize_value(&SerializeableParamVec(inner_tuple))?,
+			false => map.serialize_value(components)?,
+		}
 	}
 	Ok(())
 }
@@ -199,8 +248,8 @@ mod tests {
 	#[cfg(not(feature = "std"))]
 	use crate::no_std_prelude::*;
 	use crate::{
-		tests::{assert_json_eq, assert_ser_de},
-		Param, ParamType,
+		tests::{assert_json_eq, assert_ser_de, component},
+		Param, ParamType, TupleParam,
 	};
 
 	#[test]
@@ -212,7 +261,10 @@ mod tests {
 
 		let deserialized: Param = serde_json::from_str(s).unwrap();
 
-		assert_eq!(deserialized, Param { name: "foo".to_owned(), kind: ParamType::Address, internal_type: None });
+		assert_eq!(
+			deserialized,
+			Param { name: "foo".to_owned(), kind: ParamType::Address, internal_type: None, components: vec![] }
+		);
 
 		assert_json_eq(s, serde_json::to_string(&deserialized).unwrap().as_str());
 	}
@@ -232,7 +284,8 @@ mod tests {
 			Param {
 				name: "foo".to_owned(),
 				kind: ParamType::Address,
-				internal_type: Some("struct Verifier.Proof".to_string())
+				internal_type: Some("struct Verifier.Proof".to_string()),
+				components: vec![]
 			}
 		);
 
@@ -266,7 +319,11 @@ mod tests {
 			Param {
 				name: "foo".to_owned(),
 				kind: ParamType::Tuple(vec![ParamType::Uint(48), ParamType::Tuple(vec![ParamType::Address])]),
-				internal_type: None
+				internal_type: None,
+				components: vec![
+					component(ParamType::Uint(48), vec![]),
+					component(ParamType::Tuple(vec![ParamType::Address]), vec![component(ParamType::Address, vec![])]),
+				]
 			}
 		);
 
@@ -301,7 +358,11 @@ mod tests {
 			Param {
 				name: "foo".to_owned(),
 				kind: ParamType::Tuple(vec![ParamType::Uint(48), ParamType::Tuple(vec![ParamType::Address])]),
-				internal_type: Some("struct Pairing.G1Point[]".to_string())
+				internal_type: Some("struct Pairing.G1Point[]".to_string()),
+				components: vec![
+					component(ParamType::Uint(48), vec![]),
+					component(ParamType::Tuple(vec![ParamType::Address]), vec![component(ParamType::Address, vec![])]),
+				]
 			}
 		);
 
@@ -338,11 +399,32 @@ mod tests {
 			Param {
 				name: "foo".to_owned(),
 				kind: ParamType::Tuple(vec![ParamType::Uint(48), ParamType::Tuple(vec![ParamType::Address])]),
-				internal_type: None
+				internal_type: None,
+				components: vec![
+					TupleParam {
+						name: Some("amount".to_owned()),
+						kind: ParamType::Uint(48),
+						internal_type: None,
+						components: vec![],
+					},
+					TupleParam {
+						name: Some("things".to_owned()),
+						kind: ParamType::Tuple(vec![ParamType::Address]),
+						internal_type: None,
+						components: vec![TupleParam {
+							name: Some("baseTupleParam".to_owned()),
+							kind: ParamType::Address,
+							internal_type: None,
+							components: vec![],
+						}],
+					},
+				]
 			}
 		);
 
 		assert_ser_de(&deserialized);
+		// component names survive a round trip
+		assert_json_eq(s, serde_json::to_string(&deserialized).unwrap().as_str());
 	}
 
 	#[test]
@@ -374,7 +456,12 @@ mod tests {
 					ParamType::Address,
 					ParamType::Address
 				]))),
-				internal_type: None
+				internal_type: None,
+				components: vec![
+					component(ParamType::Uint(48), vec![]),
+					component(ParamType::Address, vec![]),
+					component(ParamType::Address, vec![]),
+				]
 			}
 		);
 
@@ -405,7 +492,8 @@ mod tests {
 					ParamType::Uint(8),
 					ParamType::Uint(16),
 				]))))),
-				internal_type: None
+				internal_type: None,
+				components: vec![component(ParamType::Uint(8), vec![]), component(ParamType::Uint(16), vec![])]
 			}
 		);
 
@@ -440,7 +528,12 @@ mod tests {
 					Box::new(ParamType::Tuple(vec![ParamType::Uint(48), ParamType::Address, ParamType::Address])),
 					2
 				),
-				internal_type: None
+				internal_type: None,
+				components: vec![
+					component(ParamType::Uint(48), vec![]),
+					component(ParamType::Address, vec![]),
+					component(ParamType::Address, vec![]),
+				]
 			}
 		);
 
@@ -482,10 +575,53 @@ mod tests {
 					ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::Address]))),
 					ParamType::FixedArray(Box::new(ParamType::Tuple(vec![ParamType::Address])), 42,)
 				]),
-				internal_type: None
+				internal_type: None,
+				components: vec![
+					component(
+						ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::Address]))),
+						vec![component(ParamType::Address, vec![])],
+					),
+					component(
+						ParamType::FixedArray(Box::new(ParamType::Tuple(vec![ParamType::Address])), 42),
+						vec![component(ParamType::Address, vec![])],
+					),
+				]
 			}
 		);
 
 		assert_json_eq(s, serde_json::to_string(&deserialized).unwrap().as_str());
 	}
+
+	#[test]
+	fn param_components_mismatch() {
+		let error = |s: &str| serde_json::from_str::<Param>(s).unwrap_err().to_string();
+		assert!(error(r#"{ "name": "a", "type": "(uint256)", "components": [{ "type": "bool" }] }"#)
+			.starts_with("`components` do not match the tuple in `type`"));
+		assert!(error(r#"{ "name": "a", "type": "address", "components": [{ "type": "bool" }] }"#)
+			.starts_with("`components` are only allowed for tuple types"));
+
+		let deserialized: Param = serde_json::from_str(
+			r#"{ "name": "a", "type": "(bool)", "components": [{ "name": "b", "type": "bool" }] }"#,
+		)
+		.unwrap();
+		assert_eq!(deserialized.kind, ParamType::Tuple(vec![ParamType::Bool]));
+
+		let param = Param {
+			name: "a".to_owned(),
+			kind: ParamType::Tuple(vec![ParamType::Address]),
+			internal_type: None,
+			components: vec![component(ParamType::Bool, vec![])],
+		};
+		assert_eq!(
+			serde_json::to_string(&param).unwrap_err().to_string(),
+			"`components` do not match the tuple in `kind`"
+		);
+
+		// without components, tuples are written unnamed
+		let param = Param { components: vec![], ..param };
+		assert_json_eq(
+			r#"{ "name": "a", "type": "tuple", "components": [{ "type": "address" }] }"#,
+			&serde_json::to_string(&param).unwrap(),
+		);
+	}
 }
@@ -29,10 +29,15 @@ pub fn long_signature(name: &str, params: &[ParamType]) -> Hash {
 	result.into()
 }
 
-fn fill_signature(name: &str, params: &[ParamType], result: &mut [u8]) {
+/// Returns the canonical `name(type1,type2,...)` form of the signature that gets hashed
+pub fn canonical_signature(name: &str, params: &[ParamType]) -> String {
 	let types = params.iter().map(Writer::write).collect::<Vec<String>>().join(",");
 
-	let data: Vec<u8> = From::from(format!("{name}({types})").as_str());
+	format!("{name}({types})")
+}
+
+fn fill_signature(name: &str, params: &[ParamType], result: &mut [u8]) {
+	let data: Vec<u8> = From::from(canonical_signature(name, params).as_str());
 
 	result.copy_from_slice(&Keccak256::digest(data)[..result.len()])
 }
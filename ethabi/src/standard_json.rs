@@ -0,0 +1,94 @@
+// Copyright 2015-2020 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Output of `solc --standard-json`.
+
+use std::{collections::BTreeMap, io};
+
+use serde::Deserialize;
+
+use crate::{errors, Artifact, Error};
+
+/// Contracts compiled by `solc --standard-json`, keyed by source file and contract name.
+///
+/// Every contract is loaded as an [`Artifact`], with bytecode and method identifiers taken from its `evm`
+/// section, so the output selection decides what is available.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+pub struct StandardJsonOutput {
+	/// Compiled contracts: source file name to contract name to artifact.
+	#[serde(default)]
+	pub contracts: BTreeMap<String, BTreeMap<String, Artifact>>,
+}
+
+impl StandardJsonOutput {
+	/// Loads standard json output.
+	pub fn load<T: io::Read>(reader: T) -> errors::Result<Self> {
+		serde_json::from_reader(reader).map_err(From::from)
+	}
+
+	/// Iterates over all contracts, addressed as `file:Name`.
+	pub fn contracts(&self) -> impl Iterator<Item = (String, &Artifact)> {
+		self.contracts.iter().flat_map(|(file, contracts)| {
+			contracts.iter().map(move |(name, artifact)| (format!("{}:{}", file, name), artifact))
+		})
+	}
+
+	/// Returns the contract addressed as `file:Name`, or as `Name` if no other file has a contract of that name.
+	pub fn contract(&self, name: &str) -> errors::Result<&Artifact> {
+		if let Some((file, name)) = name.rsplit_once(':') {
+			return self
+				.contracts
+				.get(file)
+				.and_then(|contracts| contracts.get(name))
+				.ok_or_else(|| Error::InvalidName(format!("{}:{}", file, name)));
+		}
+
+		let mut found = self.contracts.values().filter_map(|contracts| contracts.get(name));
+		match (found.next(), found.next()) {
+			(Some(artifact), None) => Ok(artifact),
+			_ => Err(Error::InvalidName(name.to_owned())),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::StandardJsonOutput;
+
+	const OUTPUT: &str = r#"{
+		"errors": [],
+		"sources": { "a.sol": { "id": 0 }, "b.sol": { "id": 1 } },
+		"contracts": {
+			"a.sol": {
+				"Foo": { "abi": [], "evm": { "bytecode": { "object": "6080" } } },
+				"Bar": { "abi": [] }
+			},
+			"b.sol": {
+				"Foo": { "abi": [{ "type": "fallback", "stateMutability": "nonpayable" }] }
+			}
+		}
+	}"#;
+
+	#[test]
+	fn contracts() {
+		let output = StandardJsonOutput::load(OUTPUT.as_bytes()).unwrap();
+		let names: Vec<_> = output.contracts().map(|(name, _)| name).collect();
+		assert_eq!(names, ["a.sol:Bar", "a.sol:Foo", "b.sol:Foo"]);
+	}
+
+	#[test]
+	fn contract() {
+		let output = StandardJsonOutput::load(OUTPUT.as_bytes()).unwrap();
+		assert_eq!(output.contract("a.sol:Foo").unwrap().bytecode, Some(vec![0x60, 0x80]));
+		assert!(output.contract("b.sol:Foo").unwrap().abi.fallback);
+		assert!(output.contract("Bar").is_ok());
+		// ambiguous
+		assert!(output.contract("Foo").is_err());
+		assert!(output.contract("a.sol:Baz").is_err());
+	}
+}
@@ -17,6 +17,8 @@ use serde_json::Value;
 
 #[cfg(not(feature = "std"))]
 use crate::no_std_prelude::*;
+#[cfg(feature = "serde")]
+use crate::TupleParam;
 use crate::{decode, encode, Int, ParamType, Token, Uint};
 
 #[cfg(feature = "serde")]
@@ -36,6 +38,12 @@ where
 	assert_eq!(canon, &de);
 }
 
+/// Unnamed tuple component.
+#[cfg(feature = "serde")]
+pub(crate) fn component(kind: ParamType, components: Vec<TupleParam>) -> TupleParam {
+	TupleParam { name: None, kind, internal_type: None, components }
+}
+
 macro_rules! test_encode_decode {
 	(name: $name:tt, types: $types:expr, tokens: $tokens:expr, data: $data:tt) => {
 		paste::item! {
@@ -0,0 +1,119 @@
+// Copyright 2015-2020 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::fmt;
+
+#[cfg(not(feature = "std"))]
+use crate::no_std_prelude::*;
+use crate::{checksum_address, util::signed_decimal, Token, Uint};
+
+/// Formats a token for humans, see [`Token::human_readable`].
+#[derive(Debug, Clone, Copy)]
+pub struct HumanReadable<'a>(pub(super) &'a Token);
+
+impl fmt::Display for HumanReadable<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.0 {
+			Token::Bool(b) => write!(f, "{b}"),
+			Token::String(s) => write!(f, "{s:?}"),
+			Token::Address(a) => f.write_str(&checksum_address(a, None)),
+			Token::Bytes(bytes) | Token::FixedBytes(bytes) => write!(f, "0x{}", hex::encode(bytes)),
+			Token::Uint(i) => write_unsigned(f, i),
+			Token::Int(i) if i.bit(255) => f.write_str(&signed_decimal(i)),
+			Token::Int(i) => write_unsigned(f, i),
+			Token::Array(tokens) | Token::FixedArray(tokens) => write_list(f, "[", tokens, "]"),
+			Token::Tuple(tokens) => write_list(f, "(", tokens, ")"),
+		}
+	}
+}
+
+/// Writes decimal, except for numbers of exactly 64 digits which tokenizers reject as ambiguous.
+fn write_unsigned(f: &mut fmt::Formatter, value: &Uint) -> fmt::Result {
+	let decimal = value.to_string();
+	match decimal.len() {
+		64 => write!(f, "0x{value:x}"),
+		_ => f.write_str(&decimal),
+	}
+}
+
+fn write_list(f: &mut fmt::Formatter, open: &str, tokens: &[Token], close: &str) -> fmt::Result {
+	f.write_str(open)?;
+	for (i, token) in tokens.iter().enumerate() {
+		if i != 0 {
+			f.write_str(",")?;
+		}
+		write!(f, "{}", HumanReadable(token))?;
+	}
+	f.write_str(close)
+}
+
+#[cfg(test)]
+mod tests {
+	#[cfg(not(feature = "std"))]
+	use crate::no_std_prelude::*;
+	use crate::{Int, Token, Uint};
+
+	#[test]
+	fn format() {
+		let token = Token::Tuple(vec![
+			Token::Int(Int::MAX - 1),
+			Token::Uint(Uint::from(1000)),
+			Token::Bytes(vec![0xca, 0xfe]),
+			Token::String("a \"b\", [c]\n".into()),
+			Token::Array(vec![Token::Bool(true), Token::Bool(false)]),
+			Token::Address("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".parse().unwrap()),
+		]);
+		assert_eq!(
+			token.human_readable().to_string(),
+			r#"(-2,1000,0xcafe,"a \"b\", [c]\n",[true,false],0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed)"#
+		);
+
+		let long = Uint::from_dec_str(&"1".repeat(64)).unwrap();
+		assert_eq!(Token::Uint(long).human_readable().to_string(), format!("0x{long:x}"));
+	}
+
+	#[cfg(feature = "full-serde")]
+	#[test]
+	fn round_trip() {
+		use crate::{
+			token::{LenientTokenizer, StrictTokenizer, Tokenizer},
+			ParamType,
+		};
+
+		let cases = [
+			(ParamType::Int(256), Token::Int(Int::MAX)),
+			(ParamType::Int(256), Token::Int(Int::from(1) << 255)),
+			(ParamType::Int(8), Token::Int(Int::from(127))),
+			(ParamType::Uint(256), Token::Uint(Uint::MAX)),
+			(ParamType::Uint(256), Token::Uint(Uint::from_dec_str(&"1".repeat(64)).unwrap())),
+			(ParamType::String, Token::String("say \"hi\", 'bye' \\ \t ünï\u{7}".into())),
+			(
+				ParamType::Array(Box::new(ParamType::String)),
+				Token::Array(vec![
+					Token::String("a,b".into()),
+					Token::String("c\"".into()),
+					Token::String("ü ])".into()),
+				]),
+			),
+			(
+				ParamType::Tuple(vec![ParamType::FixedBytes(2), ParamType::Address, ParamType::Bool]),
+				Token::Tuple(vec![
+					Token::FixedBytes(vec![0xca, 0xfe]),
+					Token::Address("fb6916095ca1df60bb79ce92ce3ea74c37c5d359".parse().unwrap()),
+					Token::Bool(true),
+				]),
+			),
+		];
+
+		for (kind, token) in cases {
+			let formatted = token.human_readable().to_string();
+			assert_eq!(StrictTokenizer::tokenize(&kind, &formatted).unwrap(), token, "{formatted}");
+			assert_eq!(LenientTokenizer::tokenize(&kind, &formatted).unwrap(), token, "{formatted}");
+		}
+	}
+}
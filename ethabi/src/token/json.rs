@@ -0,0 +1,258 @@
+// Copyright 2015-2020 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::borrow::Cow;
+
+use serde_json::Value;
+
+use crate::{
+	checksum_address,
+	errors::Error,
+	token::{StrictTokenizer, Token, Tokenizer},
+	util::signed_decimal,
+	Int, ParamType, TupleParam, Uint,
+};
+
+/// Converts a json value to a token of type `kind`.
+///
+/// Ints are json numbers or decimal or `0x` prefixed hex strings, addresses and bytes hex strings, arrays json
+/// arrays and tuples json arrays or objects keyed by component position.
+pub fn from_json(kind: &ParamType, value: &Value) -> Result<Token, Error> {
+	from_json_named(kind, &[], value)
+}
+
+/// Converts a json value to a token of type `kind`, like [`from_json`], with tuples also given as objects keyed by
+/// the names of their `components`.
+pub fn from_json_named(kind: &ParamType, components: &[TupleParam], value: &Value) -> Result<Token, Error> {
+	match (kind, value) {
+		(ParamType::Address | ParamType::Bytes | ParamType::FixedBytes(_), Value::String(hex)) => {
+			StrictTokenizer::tokenize(kind, hex)
+		}
+		(ParamType::Bool, Value::Bool(value)) => Ok(Token::Bool(*value)),
+		(ParamType::Bool, Value::String(value)) => StrictTokenizer::tokenize(kind, value),
+		(ParamType::String, Value::String(value)) => Ok(Token::String(value.clone())),
+		(ParamType::Uint(size), _) => {
+			let (negative, value) = json_integer(value)?;
+			match negative || value.bits() > *size {
+				true => Err(out_of_range(kind, value, negative)),
+				false => Ok(Token::Uint(value)),
+			}
+		}
+		(ParamType::Int(size), _) => {
+			let (negative, value) = json_integer(value)?;
+			let bound = Int::one() << size.saturating_sub(1);
+			match (negative, value) {
+				(false, value) if value < bound => Ok(Token::Int(value)),
+				(true, value) if value <= bound => Ok(Token::Int((!value).overflowing_add(Int::one()).0)),
+				(negative, value) => Err(out_of_range(kind, value, negative)),
+			}
+		}
+		(ParamType::Array(inner), Value::Array(values)) => values
+			.iter()
+			.map(|value| from_json_named(inner, components, value))
+			.collect::<Result<_, _>>()
+			.map(Token::Array),
+		(ParamType::FixedArray(inner, len), Value::Array(values)) => {
+			if values.len() != *len {
+				return Err(Error::Other(Cow::Owned(format!(
+					"expected {len} elements for {kind}, got {}",
+					values.len()
+				))));
+			}
+			values
+				.iter()
+				.map(|value| from_json_named(inner, components, value))
+				.collect::<Result<_, _>>()
+				.map(Token::FixedArray)
+		}
+		(ParamType::Tuple(kinds), Value::Array(values)) => {
+			if values.len() != kinds.len() {
+				return Err(Error::Other(Cow::Owned(format!(
+					"expected {} components for {kind}, got {}",
+					kinds.len(),
+					values.len()
+				))));
+			}
+			let components = named_components(kinds, components);
+			kinds
+				.iter()
+				.zip(values)
+				.enumerate()
+				.map(|(index, (kind, value))| {
+					from_json_named(kind, components.map(|c| &c[index].components[..]).unwrap_or_default(), value)
+				})
+				.collect::<Result<_, _>>()
+				.map(Token::Tuple)
+		}
+		(ParamType::Tuple(kinds), Value::Object(values)) => {
+			let components = named_components(kinds, components);
+			let mut tokens = Vec::with_capacity(kinds.len());
+			for (index, kind) in kinds.iter().enumerate() {
+				let name = match components.and_then(|c| c[index].name.as_deref()).filter(|name| !name.is_empty()) {
+					Some(name) => Cow::Borrowed(name),
+					None => Cow::Owned(index.to_string()),
+				};
+				let value = values
+					.get(name.as_ref())
+					.ok_or_else(|| Error::Other(Cow::Owned(format!("missing component `{name}` of {kind}"))))?;
+				let nested = components.map(|c| &c[index].components[..]).unwrap_or_default();
+				tokens.push(from_json_named(kind, nested, value)?);
+			}
+			if values.len() != kinds.len() {
+				return Err(Error::Other(Cow::Owned(format!("unknown components given for {kind}"))));
+			}
+			Ok(Token::Tuple(tokens))
+		}
+		(kind, value) => Err(Error::Other(Cow::Owned(format!("expected {kind}, got {value}")))),
+	}
+}
+
+/// Converts a token to canonical json: numbers are decimal strings, addresses checksummed, bytes `0x` prefixed hex,
+/// arrays and tuples json arrays.
+///
+/// Use [`NamedToken`](crate::NamedToken) to get tuples as objects keyed by component names.
+pub fn to_json(token: &Token) -> Value {
+	match token {
+		Token::Address(address) => Value::String(checksum_address(address, None)),
+		Token::Bytes(bytes) | Token::FixedBytes(bytes) => Value::String(format!("0x{}", hex::encode(bytes))),
+		Token::Uint(value) => Value::String(value.to_string()),
+		Token::Int(value) => Value::String(signed_decimal(value)),
+		Token::Bool(value) => Value::Bool(*value),
+		Token::String(value) => Value::String(value.clone()),
+		Token::Array(tokens) | Token::FixedArray(tokens) | Token::Tuple(tokens) => {
+			Value::Array(tokens.iter().map(to_json).collect())
+		}
+	}
+}
+
+/// Components which name the tuple of `kinds`, if they match it.
+fn named_components<'a>(kinds: &[ParamType], components: &'a [TupleParam]) -> Option<&'a [TupleParam]> {
+	match components.len() == kinds.len() && components.iter().zip(kinds).all(|(c, kind)| c.kind == *kind) {
+		true => Some(components),
+		false => None,
+	}
+}
+
+/// Parses a json number or a decimal or `0x` prefixed hex string, optionally negative, to its sign and magnitude.
+fn json_integer(value: &Value) -> Result<(bool, Uint), Error> {
+	match value {
+		Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+			(Some(value), _) => Ok((false, Uint::from(value))),
+			(None, Some(value)) => Ok((true, Uint::from(value.unsigned_abs()))),
+			// serde_json has already rounded it
+			_ => Err(Error::Other(Cow::Owned(format!("{number} is not an exact integer, use a string")))),
+		},
+		Value::String(value) => {
+			let (negative, magnitude) = match value.strip_prefix('-') {
+				Some(magnitude) => (true, magnitude),
+				None => (false, value.as_str()),
+			};
+			let magnitude = match magnitude.strip_prefix("0x") {
+				Some(hex) if hex.len() <= 64 => Uint::from_big_endian(&hex::decode(format!("{hex:0>64}"))?),
+				Some(_) => return Err(Error::Other(Cow::Owned(format!("{value} is longer than 256 bits")))),
+				None => Uint::from_dec_str(magnitude)?,
+			};
+			Ok((negative && !magnitude.is_zero(), magnitude))
+		}
+		value => Err(Error::Other(Cow::Owned(format!("expected integer, got {value}")))),
+	}
+}
+
+fn out_of_range(kind: &ParamType, value: Uint, negative: bool) -> Error {
+	let sign = if negative { "-" } else { "" };
+	Error::Other(Cow::Owned(format!("{sign}{value} is out of range for {kind}")))
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json::json;
+
+	use super::{from_json, from_json_named, to_json};
+	use crate::{Int, ParamType, Token, TupleParam, Uint};
+
+	#[test]
+	fn ints() {
+		let uint8 = ParamType::Uint(8);
+		assert_eq!(from_json(&uint8, &json!(255)).unwrap(), Token::Uint(255.into()));
+		assert_eq!(from_json(&uint8, &json!("255")).unwrap(), Token::Uint(255.into()));
+		assert_eq!(from_json(&uint8, &json!("0xff")).unwrap(), Token::Uint(255.into()));
+		assert!(from_json(&uint8, &json!(256)).is_err());
+		assert!(from_json(&uint8, &json!(-1)).is_err());
+		assert!(from_json(&uint8, &json!(1.5)).is_err());
+
+		let int8 = ParamType::Int(8);
+		assert_eq!(from_json(&int8, &json!(-128)).unwrap(), Token::Int(Int::MAX - 127));
+		assert_eq!(from_json(&int8, &json!("-0x80")).unwrap(), Token::Int(Int::MAX - 127));
+		assert_eq!(from_json(&int8, &json!("127")).unwrap(), Token::Int(127.into()));
+		assert_eq!(from_json(&int8, &json!("-0")).unwrap(), Token::Int(0.into()));
+		assert!(from_json(&int8, &json!(128)).is_err());
+		assert!(from_json(&int8, &json!(-129)).is_err());
+
+		let uint256 = ParamType::Uint(256);
+		let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+		assert_eq!(from_json(&uint256, &json!(max)).unwrap(), Token::Uint(Uint::MAX));
+		assert_eq!(to_json(&Token::Uint(Uint::MAX)), json!(max));
+		assert_eq!(to_json(&Token::Int(Int::MAX - 127)), json!("-128"));
+	}
+
+	#[test]
+	fn addresses_and_bytes() {
+		let address = json!("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+		let token = from_json(&ParamType::Address, &address).unwrap();
+		assert_eq!(token, Token::Address("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".parse().unwrap()));
+		assert_eq!(to_json(&token), address);
+
+		assert_eq!(from_json(&ParamType::Bytes, &json!("0xcafe")).unwrap(), Token::Bytes(vec![0xca, 0xfe]));
+		assert_eq!(from_json(&ParamType::FixedBytes(2), &json!("cafe")).unwrap(), Token::FixedBytes(vec![0xca, 0xfe]));
+		assert!(from_json(&ParamType::FixedBytes(3), &json!("0xcafe")).is_err());
+		assert_eq!(to_json(&Token::FixedBytes(vec![0xca, 0xfe])), json!("0xcafe"));
+		assert!(from_json(&ParamType::Address, &json!(1)).is_err());
+	}
+
+	#[test]
+	fn arrays_and_tuples() {
+		let kind = ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::Bool, ParamType::String])));
+		let token = Token::Array(vec![Token::Tuple(vec![Token::Bool(true), Token::String("a".into())])]);
+		assert_eq!(from_json(&kind, &json!([[true, "a"]])).unwrap(), token);
+		assert_eq!(from_json(&kind, &json!([{ "0": true, "1": "a" }])).unwrap(), token);
+		assert_eq!(to_json(&token), json!([[true, "a"]]));
+		assert!(from_json(&kind, &json!([[true]])).is_err());
+		assert!(from_json(&kind, &json!([{ "0": true, "1": "a", "2": 3 }])).is_err());
+
+		let fixed = ParamType::FixedArray(Box::new(ParamType::Bool), 2);
+		assert_eq!(
+			from_json(&fixed, &json!([true, false])).unwrap(),
+			Token::FixedArray(vec![Token::Bool(true), Token::Bool(false)])
+		);
+		assert!(from_json(&fixed, &json!([true])).is_err());
+	}
+
+	#[test]
+	fn named_tuples() {
+		let component =
+			|name: &str, kind| TupleParam { name: Some(name.into()), kind, internal_type: None, components: vec![] };
+		let components = [component("maker", ParamType::Address), component("amount", ParamType::Uint(256))];
+		let kind = ParamType::Tuple(vec![ParamType::Address, ParamType::Uint(256)]);
+
+		let token = from_json_named(
+			&kind,
+			&components,
+			&json!({ "maker": "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed", "amount": "1000" }),
+		)
+		.unwrap();
+		assert_eq!(
+			token,
+			Token::Tuple(vec![
+				Token::Address("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".parse().unwrap()),
+				Token::Uint(1000.into()),
+			])
+		);
+		assert!(from_json_named(&kind, &components, &json!({ "maker": "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed" }))
+			.is_err());
+	}
+}
@@ -8,21 +8,27 @@
 
 use crate::{
 	errors::Error,
-	token::{StrictTokenizer, Tokenizer},
+	token::{strict::signed_word, StrictTokenizer, Tokenizer},
 	Uint,
 };
+use ethereum_types::FromDecStrErr;
 use std::borrow::Cow;
 
 use once_cell::sync::Lazy;
-static RE: Lazy<regex::Regex> =
-	Lazy::new(|| regex::Regex::new(r"^([0-9]+)(\.[0-9]+)?\s*(ether|gwei|nanoether|nano|wei)$").expect("invalid regex"));
+static RE: Lazy<regex::Regex> = Lazy::new(|| {
+	regex::Regex::new(
+		r"^([0-9](?:_?[0-9])*)(?:\.([0-9](?:_?[0-9])*))?(?:[eE]([+-]?[0-9]{1,4}))?\s*(?:([a-z]+)|@([0-9]{1,2}))?$",
+	)
+	.expect("invalid regex")
+});
 
 /// Tries to parse string as a token. Does not require string to clearly represent the value.
 pub struct LenientTokenizer;
 
 impl Tokenizer for LenientTokenizer {
+	// Mixed case is accepted without checking the checksum.
 	fn tokenize_address(value: &str) -> Result<[u8; 20], Error> {
-		StrictTokenizer::tokenize_address(value)
+		StrictTokenizer::tokenize_address(&value.to_ascii_lowercase())
 	}
 
 	fn tokenize_string(value: &str) -> Result<String, Error> {
@@ -41,84 +47,78 @@ impl Tokenizer for LenientTokenizer {
 		StrictTokenizer::tokenize_fixed_bytes(value, len)
 	}
 
+	// Anything the strict tokenizer accepts, or an amount, see `tokenize_amount`. Bare words of 64 digits are
+	// never amounts, so they fail the same way as with the strict tokenizer.
 	fn tokenize_uint(value: &str) -> Result<[u8; 32], Error> {
 		let result = StrictTokenizer::tokenize_uint(value);
-		if result.is_ok() {
+		if result.is_ok() || value.len() == 64 && value.bytes().all(|b| b.is_ascii_hexdigit()) {
 			return result;
 		}
-
-		// Tries to parse it as is first. If it fails, tries to check for
-		// expectable units with the following format: 'Number[Spaces]Unit'.
-		//   If regex fails, then the original FromDecStrErr should take priority
-		let uint = match Uint::from_dec_str(value) {
-			Ok(_uint) => _uint,
-			Err(dec_error) => {
-				let original_dec_error = dec_error.to_string();
-
-				match RE.captures(value) {
-					Some(captures) => {
-						let integer = captures.get(1).expect("capture group does not exist").as_str();
-						let fract = captures.get(2).map(|c| c.as_str().trim_start_matches('.')).unwrap_or_else(|| "");
-						let units = captures.get(3).expect("capture group does not exist").as_str();
-
-						let units = Uint::from(match units.to_lowercase().as_str() {
-							"ether" => 18,
-							"gwei" | "nano" | "nanoether" => 9,
-							"wei" => 0,
-							_ => return Err(dec_error.into()),
-						});
-
-						let integer = Uint::from_dec_str(integer)?.checked_mul(Uint::from(10u32).pow(units));
-
-						if fract.is_empty() {
-							integer.ok_or(dec_error)?
-						} else {
-							// makes sure we don't go beyond 18 decimals
-							let fract_pow = units.checked_sub(Uint::from(fract.len())).ok_or(dec_error)?;
-
-							let fract = Uint::from_dec_str(fract)?
-								.checked_mul(Uint::from(10u32).pow(fract_pow))
-								.ok_or_else(|| Error::Other(Cow::Owned(original_dec_error.clone())))?;
-
-							integer
-								.and_then(|integer| integer.checked_add(fract))
-								.ok_or(Error::Other(Cow::Owned(original_dec_error)))?
-						}
-					}
-					None => return Err(dec_error.into()),
-				}
-			}
-		};
-
-		Ok(uint.to_big_endian())
+		Ok(tokenize_amount(value)?.to_big_endian())
 	}
 
-	// We don't have a proper signed int 256-bit long type, so here we're cheating. We build a U256
-	// out of it and check that it's within the lower/upper bound of a hypothetical I256 type: half
-	// the `U256::max_value().
+	// Anything the strict tokenizer accepts, or any notation of `tokenize_uint` with an optional sign.
 	fn tokenize_int(value: &str) -> Result<[u8; 32], Error> {
 		let result = StrictTokenizer::tokenize_int(value);
 		if result.is_ok() {
 			return result;
 		}
+		match value.strip_prefix('-') {
+			Some(abs) => signed_word(true, Uint::from_big_endian(&Self::tokenize_uint(abs)?)),
+			None => signed_word(false, tokenize_amount(value)?),
+		}
+	}
+}
 
-		let abs = Uint::from_dec_str(value.trim_start_matches('-'))?;
-		let max = Uint::max_value() / 2;
-		let int = if value.starts_with('-') {
-			if abs.is_zero() {
-				return Ok(abs.to_big_endian());
-			} else if abs > max + 1 {
-				return Err(Error::Other(Cow::Borrowed("int256 parse error: Underflow")));
-			}
-			!abs + 1 // two's complement
-		} else {
-			if abs > max {
-				return Err(Error::Other(Cow::Borrowed("int256 parse error: Overflow")));
+/// Parses an amount of the form `Number[Exponent][Spaces][Unit]`, e.g. `1_000_000`, `1.5e18`, `0.1 gwei` or
+/// `100.5 @6`, where `@6` stands for a token with 6 decimals. The amount is scaled to the smallest unit
+/// exactly, a fraction of it is an error rather than being rounded.
+fn tokenize_amount(value: &str) -> Result<Uint, Error> {
+	let captures = RE.captures(value).ok_or(FromDecStrErr::InvalidCharacter)?;
+	let integer = captures[1].replace('_', "");
+	let fract = captures.get(2).map(|c| c.as_str().replace('_', "")).unwrap_or_default();
+	let exponent: i64 = captures.get(3).map_or(Ok(0), |c| c.as_str().parse())?;
+	let decimals: i64 = match (captures.get(4), captures.get(5)) {
+		(Some(unit), _) => match unit.as_str() {
+			"ether" => 18,
+			"finney" => 15,
+			"szabo" => 12,
+			"gwei" | "nano" | "nanoether" => 9,
+			"mwei" => 6,
+			"kwei" => 3,
+			"wei" => 0,
+			_ => return Err(FromDecStrErr::InvalidCharacter.into()),
+		},
+		(None, Some(decimals)) => decimals.as_str().parse()?,
+		(None, None) => 0,
+	};
+
+	// the value is `digits * 10^shift`
+	let digits = integer + &fract;
+	let shift = exponent + decimals - fract.len() as i64;
+	let (digits, shift) = match shift < 0 {
+		true => {
+			let (digits, dropped) = digits.split_at(digits.len().saturating_sub(shift.unsigned_abs() as usize));
+			if dropped.bytes().any(|b| b != b'0') {
+				return Err(Error::Other(Cow::Owned(format!(
+					"Precision error: {value} is not a whole number of the smallest unit"
+				))));
 			}
-			abs
-		};
-		Ok(int.to_big_endian())
+			(digits, 0)
+		}
+		false => (digits.as_str(), shift),
+	};
+
+	let overflow = || Error::Other(Cow::Borrowed("Uint parse error: Overflow"));
+	let digits = digits.trim_start_matches('0');
+	if digits.is_empty() {
+		return Ok(Uint::zero());
 	}
+	let mantissa = Uint::from_dec_str(digits).map_err(|_| overflow())?;
+	if shift > 77 {
+		return Err(overflow());
+	}
+	mantissa.checked_mul(Uint::from(10u32).pow(Uint::from(shift))).ok_or_else(overflow)
 }
 
 #[cfg(test)]
@@ -128,7 +128,7 @@ mod tests {
 	use crate::{
 		errors::Error,
 		token::{LenientTokenizer, Token, Tokenizer},
-		ParamType, Uint,
+		Int, ParamType, Uint,
 	};
 
 	#[test]
@@ -136,7 +136,7 @@ mod tests {
 		assert_eq!(
 			LenientTokenizer::tokenize(
 				&ParamType::Uint(256),
-				"1111111111111111111111111111111111111111111111111111111111111111"
+				"0x1111111111111111111111111111111111111111111111111111111111111111"
 			)
 			.unwrap(),
 			Token::Uint(Uint::from_big_endian(&[0x11u8; 32]))
@@ -251,4 +251,54 @@ mod tests {
 
 		assert!(matches!(LenientTokenizer::tokenize(&ParamType::Uint(256), "1gwei 1 gwei"), Err(_error)));
 	}
+
+	#[test]
+	fn tokenize_uint_notations() {
+		let uint = |value: &str| LenientTokenizer::tokenize(&ParamType::Uint(256), value).unwrap();
+		let wei = |value: &str| Token::Uint(Uint::from_dec_str(value).unwrap());
+
+		assert_eq!(uint("1_000_000"), wei("1000000"));
+		assert_eq!(uint("1.5e18"), wei("1500000000000000000"));
+		assert_eq!(uint("15E-1 ether"), wei("1500000000000000000"));
+		assert_eq!(uint("1_000.000_5e4"), wei("10000005"));
+		assert_eq!(uint("0x0de0b6b3a7640000"), wei("1000000000000000000"));
+		assert_eq!(uint("2 finney"), wei("2000000000000000"));
+		assert_eq!(uint("2szabo"), wei("2000000000000"));
+		assert_eq!(uint("100.5 @6"), wei("100500000"));
+		assert_eq!(uint("100.50@6"), wei("100500000"));
+		assert_eq!(uint("0.000e9999"), wei("0"));
+		assert_eq!(uint("1e77"), wei(&format!("1{}", "0".repeat(77))));
+	}
+
+	#[test]
+	fn tokenize_uint_notation_errors() {
+		let message = |value: &str| LenientTokenizer::tokenize_uint(value).unwrap_err().to_string();
+
+		assert_eq!(
+			message("100.0000005 @6"),
+			"Precision error: 100.0000005 @6 is not a whole number of the smallest unit"
+		);
+		assert_eq!(message("1.5e-1"), "Precision error: 1.5e-1 is not a whole number of the smallest unit");
+		assert_eq!(message("1e78"), "Uint parse error: Overflow");
+		assert_eq!(message("200000 @72"), "Uint parse error: Overflow");
+		assert_eq!(message("1__000"), "Uint parse error: InvalidCharacter");
+		assert_eq!(message("_1"), "Uint parse error: InvalidCharacter");
+		assert_eq!(message("1 lovelace"), "Uint parse error: InvalidCharacter");
+		assert_eq!(message("1 ether @6"), "Uint parse error: InvalidCharacter");
+		assert_eq!(message("-1"), "Uint parse error: InvalidCharacter");
+	}
+
+	#[test]
+	fn tokenize_int_notations() {
+		let int = |value: &str| LenientTokenizer::tokenize(&ParamType::Int(256), value).unwrap();
+
+		assert_eq!(int("-1.5 gwei"), Token::Int(!Int::from(1_500_000_000u64) + 1));
+		assert_eq!(int("-2_000 @3"), Token::Int(!Int::from(2_000_000u64) + 1));
+		assert_eq!(int("1e3"), Token::Int(Int::from(1000)));
+		assert_eq!(int("-0.0 ether"), Token::Int(Int::zero()));
+		assert_eq!(int("-0x10"), Token::Int(!Int::from(16) + 1));
+		assert_eq!(int("0x10"), Token::Int(Int::from(16)));
+		assert_eq!(LenientTokenizer::tokenize_int("-1e77").unwrap_err().to_string(), "int256 parse error: Underflow");
+		assert!(LenientTokenizer::tokenize_int("-0.5").is_err());
+	}
 }
@@ -0,0 +1,294 @@
+// Copyright 2015-2020 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Lexer for array and tuple literals, e.g. `[(1, "a, b"), (2, 'c')]`.
+
+#[cfg(not(feature = "std"))]
+use crate::no_std_prelude::*;
+use crate::{no_std_prelude::Cow, token::Tokenizer, Error, ParamType, Token};
+
+/// Parsed literal. Positions are character offsets into the parsed value.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Literal {
+	/// Unquoted value, without surrounding whitespace.
+	Plain { text: String, pos: usize },
+	/// Single or double quoted string, unescaped.
+	Quoted { text: String, pos: usize },
+	/// Array `[..]` or tuple `(..)`.
+	List { open: char, items: Vec<Literal>, pos: usize },
+}
+
+impl Literal {
+	/// Returns the items of an array, opened by `[`, or of a tuple, opened by `(`.
+	pub(crate) fn items(&self, open: char) -> Result<&[Literal], Error> {
+		match self {
+			Literal::List { open: o, items, .. } if *o == open => Ok(items),
+			literal => Err(error(format!("expected `{open}`"), literal.pos())),
+		}
+	}
+
+	/// Returns the items of an array of `len` elements.
+	pub(crate) fn fixed_items(&self, len: usize) -> Result<&[Literal], Error> {
+		let items = self.items('[')?;
+		match items.len() == len {
+			true => Ok(items),
+			false => Err(error(format!("expected {len} elements, found {}", items.len()), self.pos())),
+		}
+	}
+
+	fn pos(&self) -> usize {
+		match self {
+			Literal::Plain { pos, .. } | Literal::Quoted { pos, .. } | Literal::List { pos, .. } => *pos,
+		}
+	}
+}
+
+/// Parses a whole value as a literal.
+pub(crate) fn parse(value: &str) -> Result<Literal, Error> {
+	let mut lexer = Lexer { chars: value.chars().collect(), pos: 0 };
+	let literal = lexer.literal()?;
+	lexer.skip_whitespace();
+	match lexer.peek() {
+		Some(c) => Err(error(format!("unexpected `{c}`"), lexer.pos)),
+		None => Ok(literal),
+	}
+}
+
+/// Unquotes and unescapes a whole value which is a single or double quoted string, `None` if the string ends
+/// before the end of the value, as in `"a" and "b"`.
+#[cfg(feature = "full-serde")]
+pub(crate) fn unquote(value: &str) -> Result<Option<String>, Error> {
+	let mut lexer = Lexer { chars: value.chars().collect(), pos: 0 };
+	let text = match lexer.literal()? {
+		Literal::Quoted { text, .. } => text,
+		literal => return Err(error("expected quoted string".into(), literal.pos())),
+	};
+	match lexer.peek() {
+		Some(_) => Ok(None),
+		None => Ok(Some(text)),
+	}
+}
+
+/// Converts a literal to a token of type `param`. Values are parsed by `T`, only strings may be quoted.
+pub(crate) fn tokenize<T: Tokenizer + ?Sized>(param: &ParamType, literal: &Literal) -> Result<Token, Error> {
+	match (param, literal) {
+		(ParamType::Array(inner), literal) => tokenize_items::<T>(literal.items('[')?, inner).map(Token::Array),
+		(ParamType::FixedArray(inner, len), literal) => {
+			tokenize_items::<T>(literal.fixed_items(*len)?, inner).map(Token::FixedArray)
+		}
+		(ParamType::Tuple(params), literal) => tokenize_components::<T>(literal, params).map(Token::Tuple),
+		(ParamType::String, Literal::Quoted { text, .. }) => Ok(Token::String(text.clone())),
+		(_, Literal::Quoted { pos, .. }) => Err(error(format!("expected {param}, only strings may be quoted"), *pos)),
+		(_, Literal::List { pos, .. }) => Err(error(format!("expected {param}, found a list"), *pos)),
+		(_, Literal::Plain { text, pos }) => {
+			T::tokenize(param, text).map_err(|err| {
+				// errors only implement `Display` with `std`
+				#[cfg(feature = "std")]
+				let message = format!("invalid {param} `{text}`: {err}");
+				#[cfg(not(feature = "std"))]
+				let message = format!("invalid {param} `{text}`: {err:?}");
+				error(message, *pos)
+			})
+		}
+	}
+}
+
+/// Converts the items of an array to tokens of type `param`.
+pub(crate) fn tokenize_items<T: Tokenizer + ?Sized>(items: &[Literal], param: &ParamType) -> Result<Vec<Token>, Error> {
+	items.iter().map(|item| tokenize::<T>(param, item)).collect()
+}
+
+/// Converts the components of a tuple to tokens of types `params`.
+pub(crate) fn tokenize_components<T: Tokenizer + ?Sized>(
+	literal: &Literal,
+	params: &[ParamType],
+) -> Result<Vec<Token>, Error> {
+	let items = literal.items('(')?;
+	if items.len() != params.len() {
+		return Err(error(format!("expected {} components, found {}", params.len(), items.len()), literal.pos()));
+	}
+	params.iter().zip(items).map(|(param, item)| tokenize::<T>(param, item)).collect()
+}
+
+fn error(message: String, pos: usize) -> Error {
+	Error::Other(Cow::Owned(format!("{message} at position {pos}")))
+}
+
+struct Lexer {
+	chars: Vec<char>,
+	pos: usize,
+}
+
+impl Lexer {
+	fn peek(&self) -> Option<char> {
+		self.chars.get(self.pos).copied()
+	}
+
+	fn next(&mut self) -> Option<char> {
+		let c = self.peek();
+		self.pos += 1;
+		c
+	}
+
+	fn skip_whitespace(&mut self) {
+		while self.peek().is_some_and(char::is_whitespace) {
+			self.pos += 1;
+		}
+	}
+
+	fn literal(&mut self) -> Result<Literal, Error> {
+		self.skip_whitespace();
+		match self.peek() {
+			Some(open @ ('[' | '(')) => self.list(open),
+			Some(quote @ ('"' | '\'')) => self.quoted(quote),
+			Some(_) => self.plain(),
+			None => Err(error("expected value".into(), self.pos)),
+		}
+	}
+
+	fn list(&mut self, open: char) -> Result<Literal, Error> {
+		let pos = self.pos;
+		let close = if open == '[' { ']' } else { ')' };
+		self.pos += 1;
+
+		let mut items = Vec::new();
+		self.skip_whitespace();
+		if self.peek() == Some(close) {
+			self.pos += 1;
+			return Ok(Literal::List { open, items, pos });
+		}
+
+		loop {
+			items.push(self.literal()?);
+			self.skip_whitespace();
+			match self.next() {
+				Some(',') => continue,
+				Some(c) if c == close => return Ok(Literal::List { open, items, pos }),
+				Some(c) => return Err(error(format!("expected `,` or `{close}`, found `{c}`"), self.pos - 1)),
+				None => return Err(error(format!("`{open}` is not closed"), pos)),
+			}
+		}
+	}
+
+	fn quoted(&mut self, quote: char) -> Result<Literal, Error> {
+		let pos = self.pos;
+		self.pos += 1;
+
+		let mut text = String::new();
+		loop {
+			match self.next() {
+				Some(c) if c == quote => return Ok(Literal::Quoted { text, pos }),
+				Some('\\') => text.push(self.escape()?),
+				Some(c) => text.push(c),
+				None => return Err(error("string is not closed".into(), pos)),
+			}
+		}
+	}
+
+	/// Resolves the escapes which `Debug` of a string produces: `\"`, `\'`, `\\`, `\n`, `\r`, `\t`, `\0` and `\u{..}`.
+	fn escape(&mut self) -> Result<char, Error> {
+		let pos = self.pos - 1;
+		let invalid = || error("invalid escape".into(), pos);
+		match self.next().ok_or_else(invalid)? {
+			c @ ('"' | '\'' | '\\') => Ok(c),
+			'n' => Ok('\n'),
+			'r' => Ok('\r'),
+			't' => Ok('\t'),
+			'0' => Ok('\0'),
+			'u' if self.next() == Some('{') => {
+				let mut code = String::new();
+				loop {
+					match self.next().ok_or_else(invalid)? {
+						'}' => break,
+						c => code.push(c),
+					}
+				}
+				u32::from_str_radix(&code, 16).ok().and_then(char::from_u32).ok_or_else(invalid)
+			}
+			_ => Err(invalid()),
+		}
+	}
+
+	fn plain(&mut self) -> Result<Literal, Error> {
+		let pos = self.pos;
+		let mut text = String::new();
+		while let Some(c) = self.peek() {
+			match c {
+				',' | ']' | ')' => break,
+				// apostrophes may be part of plain text, as in `don't`, quotes only start it
+				'[' | '(' | '"' => return Err(error(format!("unexpected `{c}`"), self.pos)),
+				c => text.push(c),
+			}
+			self.pos += 1;
+		}
+
+		let text = text.trim_end().to_owned();
+		match text.is_empty() {
+			true => Err(error("expected value".into(), pos)),
+			false => Ok(Literal::Plain { text, pos }),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[cfg(not(feature = "std"))]
+	use crate::no_std_prelude::*;
+
+	use super::{parse, Literal};
+	use crate::Error;
+
+	fn plain(text: &str, pos: usize) -> Literal {
+		Literal::Plain { text: text.into(), pos }
+	}
+
+	#[test]
+	fn parse_nested() {
+		assert_eq!(
+			parse(" [ (1 ether, 'a, b]'), ( 2,\"c\\\"\\u{e9}\" ) ] ").unwrap(),
+			Literal::List {
+				open: '[',
+				items: vec![
+					Literal::List {
+						open: '(',
+						items: vec![plain("1 ether", 4), Literal::Quoted { text: "a, b]".into(), pos: 13 }],
+						pos: 3
+					},
+					Literal::List {
+						open: '(',
+						items: vec![plain("2", 25), Literal::Quoted { text: "c\"é".into(), pos: 27 }],
+						pos: 23
+					},
+				],
+				pos: 1
+			}
+		);
+		assert_eq!(parse("[]").unwrap(), Literal::List { open: '[', items: vec![], pos: 0 });
+		assert_eq!(
+			parse("[don't, x]").unwrap(),
+			Literal::List { open: '[', items: vec![plain("don't", 1), plain("x", 8)], pos: 0 }
+		);
+	}
+
+	#[test]
+	fn parse_errors() {
+		// errors only implement `Display` with `std`
+		let message = |value: &str| match parse(value).unwrap_err() {
+			Error::Other(message) => message.into_owned(),
+			err => panic!("unexpected error {err:?}"),
+		};
+		assert_eq!(message("[1,,2]"), "expected value at position 3");
+		assert_eq!(message("[1,2"), "`[` is not closed at position 0");
+		assert_eq!(message("[1,2)"), "expected `,` or `]`, found `)` at position 4");
+		assert_eq!(message("[1,2]]"), "unexpected `]` at position 5");
+		assert_eq!(message("[\"a]"), "string is not closed at position 1");
+		assert_eq!(message("[a\"]"), "unexpected `\"` at position 2");
+		assert_eq!(message("['\\q']"), "invalid escape at position 2");
+		assert_eq!(message("[1,]"), "expected value at position 3");
+	}
+}
@@ -8,6 +8,11 @@
 
 //! ABI param and parsing for it.
 
+#[cfg(feature = "full-serde")]
+mod json;
+#[cfg(feature = "full-serde")]
+pub use json::{from_json, from_json_named, to_json};
+
 #[cfg(feature = "full-serde")]
 mod lenient;
 #[cfg(feature = "full-serde")]
@@ -18,13 +23,17 @@ mod strict;
 #[cfg(feature = "full-serde")]
 pub use strict::StrictTokenizer;
 
+mod human;
+pub use human::HumanReadable;
+
+#[cfg(feature = "serde")]
+mod lexer;
+
 mod token;
 pub use token::Token;
 
 #[cfg(all(feature = "serde", not(feature = "std")))]
 use crate::no_std_prelude::*;
-#[cfg(feature = "serde")]
-use core::cmp::Ordering::{Equal, Less};
 
 #[cfg(feature = "serde")]
 use crate::{Error, Int, ParamType, Uint};
@@ -56,202 +65,21 @@ pub trait Tokenizer {
 
 	/// Tries to parse a value as a vector of tokens of fixed size.
 	fn tokenize_fixed_array(value: &str, param: &ParamType, len: usize) -> Result<Vec<Token>, Error> {
-		let result = Self::tokenize_array(value, param)?;
-		match result.len() == len {
-			true => Ok(result),
-			false => Err(Error::InvalidData),
-		}
+		lexer::tokenize_items::<Self>(lexer::parse(value)?.fixed_items(len)?, param)
 	}
 
-	/// Tried to parse a struct as a vector of tokens
+	/// Tries to parse a tuple literal, e.g. `(1,"a, b")`, as a vector of tokens.
+	///
+	/// Components are separated by commas with optional whitespace around them. Strings may be single or double
+	/// quoted, with `\\`, `\"`, `\'`, `\n`, `\r`, `\t`, `\0` and `\u{..}` escapes.
 	fn tokenize_struct(value: &str, param: &[ParamType]) -> Result<Vec<Token>, Error> {
-		if !value.starts_with('(') || !value.ends_with(')') {
-			return Err(Error::InvalidData);
-		}
-
-		if value.chars().count() == 2 {
-			return Ok(vec![]);
-		}
-
-		let mut result = vec![];
-		let mut nested = 0isize;
-		let mut ignore = false;
-		let mut last_item = 1;
-
-		let mut array_nested = 0isize;
-		let mut array_item_start = 1;
-		let mut last_is_array = false;
-
-		let mut params = param.iter();
-		for (pos, ch) in value.chars().enumerate() {
-			match ch {
-				'[' if !ignore => {
-					if array_nested == 0 {
-						array_item_start = pos;
-					}
-					array_nested += 1;
-				}
-				']' if !ignore => {
-					array_nested -= 1;
-
-					if nested > 0 {
-						// still in nested tuple
-						continue;
-					}
-
-					match array_nested.cmp(&0) {
-						Less => {
-							return Err(Error::InvalidData);
-						}
-						Equal => {
-							let sub = &value[array_item_start..pos + 1];
-							let token = Self::tokenize(params.next().ok_or(Error::InvalidData)?, sub)?;
-							result.push(token);
-							last_is_array = !last_is_array;
-						}
-						_ => {}
-					}
-				}
-				_ if array_nested != 0 => continue,
-				'(' if !ignore => {
-					nested += 1;
-				}
-				')' if !ignore && last_is_array => {
-					nested -= 1;
-					last_is_array = !last_is_array;
-				}
-				')' if !ignore => {
-					nested -= 1;
-
-					match nested.cmp(&0) {
-						Less => {
-							return Err(Error::InvalidData);
-						}
-						Equal => {
-							if last_is_array {
-								last_is_array = !last_is_array;
-							} else {
-								let sub = &value[last_item..pos];
-								let token = Self::tokenize(params.next().ok_or(Error::InvalidData)?, sub)?;
-								result.push(token);
-								last_item = pos + 1;
-							}
-						}
-						_ => {}
-					}
-				}
-				'"' => {
-					ignore = !ignore;
-				}
-				',' if array_nested == 0 && nested == 1 && !ignore && last_is_array => {
-					last_is_array = !last_is_array;
-				}
-				',' if nested == 1 && !ignore => {
-					let sub = &value[last_item..pos];
-					let token = Self::tokenize(params.next().ok_or(Error::InvalidData)?, sub)?;
-					result.push(token);
-					last_item = pos + 1;
-				}
-				_ => (),
-			}
-		}
-
-		if ignore {
-			return Err(Error::InvalidData);
-		}
-
-		Ok(result)
+		lexer::tokenize_components::<Self>(&lexer::parse(value)?, param)
 	}
 
-	/// Tries to parse a value as a vector of tokens.
+	/// Tries to parse an array literal, e.g. `[1, 2]`, as a vector of tokens. Accepts the same syntax as
+	/// [`Tokenizer::tokenize_struct`].
 	fn tokenize_array(value: &str, param: &ParamType) -> Result<Vec<Token>, Error> {
-		if !value.starts_with('[') || !value.ends_with(']') {
-			return Err(Error::InvalidData);
-		}
-
-		if value.chars().count() == 2 {
-			return Ok(vec![]);
-		}
-
-		let mut result = vec![];
-		let mut nested = 0isize;
-		let mut ignore = false;
-		let mut last_item = 1;
-
-		let mut tuple_nested = 0isize;
-		let mut tuple_item_start = 1;
-		let mut last_is_tuple = false;
-		for (i, ch) in value.chars().enumerate() {
-			match ch {
-				'(' if !ignore => {
-					if tuple_nested == 0 {
-						tuple_item_start = i;
-					}
-					tuple_nested += 1;
-				}
-				')' if !ignore => {
-					tuple_nested -= 1;
-					match tuple_nested.cmp(&0) {
-						Less => {
-							return Err(Error::InvalidData);
-						}
-						Equal => {
-							let sub = &value[tuple_item_start..i + 1];
-							let token = Self::tokenize(param, sub)?;
-							result.push(token);
-							last_is_tuple = !last_is_tuple;
-						}
-						_ => {}
-					}
-				}
-				_ if tuple_nested != 0 => continue,
-				'[' if !ignore => {
-					nested += 1;
-				}
-				']' if !ignore && last_is_tuple => {
-					nested -= 1;
-					last_is_tuple = !last_is_tuple;
-				}
-				']' if !ignore => {
-					nested -= 1;
-					match nested.cmp(&0) {
-						Less => {
-							return Err(Error::InvalidData);
-						}
-						Equal => {
-							if last_is_tuple {
-								last_is_tuple = !last_is_tuple;
-							} else {
-								let sub = &value[last_item..i];
-								let token = Self::tokenize(param, sub)?;
-								result.push(token);
-								last_item = i + 1;
-							}
-						}
-						_ => {}
-					}
-				}
-				'"' => {
-					ignore = !ignore;
-				}
-				',' if tuple_nested == 0 && nested == 1 && !ignore && last_is_tuple => {
-					last_is_tuple = !last_is_tuple;
-				}
-				',' if tuple_nested == 0 && nested == 1 && !ignore => {
-					let sub = &value[last_item..i];
-					let token = Self::tokenize(param, sub)?;
-					result.push(token);
-					last_item = i + 1;
-				}
-				_ => (),
-			}
-		}
-
-		if ignore {
-			return Err(Error::InvalidData);
-		}
-
-		Ok(result)
+		lexer::tokenize_items::<Self>(lexer::parse(value)?.items('[')?, param)
 	}
 
 	/// Tries to parse a value as an address.
@@ -278,7 +106,7 @@ pub trait Tokenizer {
 
 #[cfg(all(test, feature = "full-serde"))]
 mod test {
-	use super::{LenientTokenizer, ParamType, Tokenizer};
+	use super::{LenientTokenizer, ParamType, StrictTokenizer, Tokenizer};
 	use crate::Token;
 
 	#[test]
@@ -290,6 +118,45 @@ mod test {
 		assert!(LenientTokenizer::tokenize_array("[1,0]", &ParamType::Bool).is_ok());
 	}
 
+	#[test]
+	fn quoted_strings_whitespace_and_escapes() {
+		let param = ParamType::Tuple(vec![
+			ParamType::Array(Box::new(ParamType::String)),
+			ParamType::Uint(256),
+			ParamType::String,
+		]);
+		assert_eq!(
+			StrictTokenizer::tokenize(&param, r#"( [ "a, b]", 'c)\'' , "\"d\"\n" ] , 2 , plain text )"#).unwrap(),
+			Token::Tuple(vec![
+				Token::Array(vec![
+					Token::String("a, b]".into()),
+					Token::String("c)'".into()),
+					Token::String("\"d\"\n".into()),
+				]),
+				Token::Uint(2.into()),
+				Token::String("plain text".into()),
+			])
+		);
+	}
+
+	#[test]
+	fn error_positions() {
+		let message = |param: &ParamType, value: &str| StrictTokenizer::tokenize(param, value).unwrap_err().to_string();
+		let bools = ParamType::Array(Box::new(ParamType::Bool));
+		assert_eq!(message(&bools, "[true, maybe]"), "invalid bool `maybe`: Invalid data at position 7");
+		assert_eq!(message(&bools, "[true, \"1\"]"), "expected bool, only strings may be quoted at position 7");
+		assert_eq!(message(&bools, "[true, [false]]"), "expected bool, found a list at position 7");
+		assert_eq!(message(&bools, "(true)"), "expected `[` at position 0");
+		assert_eq!(
+			message(&ParamType::Tuple(vec![ParamType::Bool, ParamType::Bool]), "(true)"),
+			"expected 2 components, found 1 at position 0"
+		);
+		assert_eq!(
+			message(&ParamType::FixedArray(Box::new(ParamType::Bool), 2), " [true]"),
+			"expected 2 elements, found 1 at position 1"
+		);
+	}
+
 	#[test]
 	fn tuples_arrays_mixed() {
 		assert_eq!(
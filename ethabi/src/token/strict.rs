@@ -8,26 +8,44 @@
 #[cfg(not(feature = "std"))]
 use crate::no_std_prelude::*;
 
-use crate::{errors::Error, token::Tokenizer};
+use crate::{
+	checksum::is_valid_checksum,
+	checksum_address,
+	errors::Error,
+	no_std_prelude::Cow,
+	token::{lexer, Tokenizer},
+	Uint,
+};
 
-/// Tries to parse string as a token. Require string to clearly represent the value.
+/// Tries to parse string as a token, rejecting values which could be read in more than one way.
+///
+/// Ints are decimal, `0x` prefixed hex or a word of 64 hex digits, strings are taken as is or double quoted
+/// with escapes, and addresses must have a valid checksum if they are mixed case.
 pub struct StrictTokenizer;
 
 impl Tokenizer for StrictTokenizer {
 	fn tokenize_address(value: &str) -> Result<[u8; 20], Error> {
 		let hex: Vec<u8> = hex::decode(value)?;
-		match hex.len() == 20 {
-			false => Err(Error::InvalidData),
-			true => {
-				let mut address = [0u8; 20];
-				address.copy_from_slice(&hex);
-				Ok(address)
-			}
+		if hex.len() != 20 {
+			return Err(Error::InvalidData);
+		}
+		let mut address = [0u8; 20];
+		address.copy_from_slice(&hex);
+		match is_valid_checksum(value, &address.into()) {
+			true => Ok(address),
+			false => Err(Error::Other(Cow::Owned(format!(
+				"Invalid address checksum: {value}, expected {}",
+				checksum_address(&address.into(), None)
+			)))),
 		}
 	}
 
+	// Double quoted strings are unquoted and unescaped, anything else is taken as is.
 	fn tokenize_string(value: &str) -> Result<String, Error> {
-		Ok(value.to_owned())
+		match value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+			true => Ok(lexer::unquote(value)?.unwrap_or_else(|| value.to_owned())),
+			false => Ok(value.to_owned()),
+		}
 	}
 
 	fn tokenize_bool(value: &str) -> Result<bool, Error> {
@@ -50,27 +68,69 @@ impl Tokenizer for StrictTokenizer {
 		}
 	}
 
+	// A word of 64 hex digits, `0x` prefixed hex or decimal. 64 decimal digits could be either, so are rejected.
 	fn tokenize_uint(value: &str) -> Result<[u8; 32], Error> {
-		let hex: Vec<u8> = hex::decode(value)?;
-		match hex.len() == 32 {
-			true => {
-				let mut uint = [0u8; 32];
-				uint.copy_from_slice(&hex);
-				Ok(uint)
-			}
-			false => Err(Error::InvalidData),
+		match value.strip_prefix("0x") {
+			Some(hex) => tokenize_word(hex),
+			None if value.len() == 64 && value.bytes().all(|b| b.is_ascii_digit()) => Err(ambiguous(value)),
+			None if value.len() == 64 => tokenize_word(value),
+			None => Ok(Uint::from_dec_str(value)?.to_big_endian()),
 		}
 	}
 
+	// A two's complement word of 64 hex digits, `0x` prefixed hex or decimal with an optional sign.
 	fn tokenize_int(value: &str) -> Result<[u8; 32], Error> {
-		Self::tokenize_uint(value)
+		match value.strip_prefix('-') {
+			Some(abs) => signed_word(true, Uint::from_dec_str(abs)?),
+			None if value.starts_with("0x") || value.len() == 64 => Self::tokenize_uint(value),
+			None => signed_word(false, Uint::from_dec_str(value)?),
+		}
 	}
 }
 
+/// Builds the two's complement word of a signed integer given by its sign and absolute value.
+///
+/// We don't have a proper signed int 256-bit long type, so here we're cheating. We build a U256
+/// out of it and check that it's within the lower/upper bound of a hypothetical I256 type: half
+/// the `U256::max_value().
+pub(crate) fn signed_word(negative: bool, abs: Uint) -> Result<[u8; 32], Error> {
+	let max = Uint::max_value() / 2;
+	let int = if negative {
+		if abs.is_zero() {
+			return Ok(abs.to_big_endian());
+		} else if abs > max + 1 {
+			return Err(Error::Other(Cow::Borrowed("int256 parse error: Underflow")));
+		}
+		!abs + 1 // two's complement
+	} else {
+		if abs > max {
+			return Err(Error::Other(Cow::Borrowed("int256 parse error: Overflow")));
+		}
+		abs
+	};
+	Ok(int.to_big_endian())
+}
+
+fn ambiguous(value: &str) -> Error {
+	Error::Other(Cow::Owned(format!(
+		"Ambiguous number {value}: 64 digits may be decimal or a hex word, prefix hex with `0x`"
+	)))
+}
+
+/// Parses up to 64 hex digits as a left padded word.
+fn tokenize_word(hex: &str) -> Result<[u8; 32], Error> {
+	if hex.is_empty() || hex.len() > 64 {
+		return Err(Error::InvalidData);
+	}
+	let mut word = [0u8; 32];
+	hex::decode_to_slice(format!("{hex:0>64}"), &mut word)?;
+	Ok(word)
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::{
-		token::{StrictTokenizer, Token, Tokenizer},
+		token::{LenientTokenizer, StrictTokenizer, Token, Tokenizer},
 		Int, ParamType, Uint,
 	};
 
@@ -86,6 +146,30 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn tokenize_address_checksum() {
+		let address = Token::Address("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".parse().unwrap());
+		assert_eq!(
+			StrictTokenizer::tokenize(&ParamType::Address, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").unwrap(),
+			address
+		);
+		assert_eq!(
+			StrictTokenizer::tokenize(&ParamType::Address, "0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED").unwrap(),
+			address
+		);
+		assert_eq!(
+			StrictTokenizer::tokenize(&ParamType::Address, "0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAed")
+				.unwrap_err()
+				.to_string(),
+			"Invalid address checksum: 5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAed, \
+			expected 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+		);
+		assert_eq!(
+			LenientTokenizer::tokenize(&ParamType::Address, "0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAed").unwrap(),
+			address
+		);
+	}
+
 	#[test]
 	fn tokenize_string() {
 		assert_eq!(
@@ -129,7 +213,7 @@ mod tests {
 		assert_eq!(
 			StrictTokenizer::tokenize(
 				&ParamType::Uint(256),
-				"1111111111111111111111111111111111111111111111111111111111111111"
+				"0x1111111111111111111111111111111111111111111111111111111111111111"
 			)
 			.unwrap(),
 			Token::Uint(Uint::from_big_endian(&[0x11u8; 32]))
@@ -138,19 +222,65 @@ mod tests {
 		assert_eq!(
 			StrictTokenizer::tokenize(
 				&ParamType::Uint(256),
-				"2222222222222222222222222222222222222222222222222222222222222222"
+				"0x2222222222222222222222222222222222222222222222222222222222222222"
 			)
 			.unwrap(),
 			Token::Uint(Uint::from_big_endian(&[0x22u8; 32]))
 		);
 	}
 
+	#[test]
+	fn tokenize_uint_decimal_and_hex() {
+		assert_eq!(StrictTokenizer::tokenize(&ParamType::Uint(256), "1000").unwrap(), Token::Uint(Uint::from(1000)));
+		assert_eq!(StrictTokenizer::tokenize(&ParamType::Uint(256), "0x3e8").unwrap(), Token::Uint(Uint::from(1000)));
+		assert!(StrictTokenizer::tokenize(&ParamType::Uint(256), "-1").is_err());
+		assert!(StrictTokenizer::tokenize(&ParamType::Uint(256), "0x").is_err());
+		assert!(StrictTokenizer::tokenize(&ParamType::Uint(256), "1 ether").is_err());
+
+		// 64 decimal digits may be a decimal number or a hex word
+		let word = format!("1{}", "0".repeat(63));
+		assert_eq!(
+			StrictTokenizer::tokenize(&ParamType::Uint(256), &word).unwrap_err().to_string(),
+			format!("Ambiguous number {word}: 64 digits may be decimal or a hex word, prefix hex with `0x`")
+		);
+		assert!(StrictTokenizer::tokenize(&ParamType::Int(256), &word).is_err());
+		assert!(LenientTokenizer::tokenize(&ParamType::Uint(256), &word).is_err());
+		assert_eq!(
+			StrictTokenizer::tokenize(&ParamType::Uint(256), &word[..63]).unwrap(),
+			Token::Uint(Uint::exp10(62))
+		);
+		assert_eq!(StrictTokenizer::tokenize(&ParamType::Uint(256), &"f".repeat(64)).unwrap(), Token::Uint(Uint::MAX));
+	}
+
+	#[test]
+	fn tokenize_int_decimal_and_hex() {
+		assert_eq!(StrictTokenizer::tokenize(&ParamType::Int(256), "-2").unwrap(), Token::Int(Int::MAX - 1));
+		assert_eq!(StrictTokenizer::tokenize(&ParamType::Int(256), "2").unwrap(), Token::Int(Int::from(2)));
+		assert_eq!(StrictTokenizer::tokenize(&ParamType::Int(256), "0x02").unwrap(), Token::Int(Int::from(2)));
+		assert!(StrictTokenizer::tokenize(&ParamType::Int(256), "-0x02").is_err());
+	}
+
+	#[test]
+	fn tokenize_quoted_string() {
+		assert_eq!(
+			StrictTokenizer::tokenize(&ParamType::String, r#""say \"hi\"\n\u{1f600}""#).unwrap(),
+			Token::String("say \"hi\"\n\u{1f600}".to_owned())
+		);
+		assert_eq!(StrictTokenizer::tokenize(&ParamType::String, r#""""#).unwrap(), Token::String(String::new()));
+		assert_eq!(
+			StrictTokenizer::tokenize(&ParamType::String, r#""a" and "b""#).unwrap(),
+			Token::String(r#""a" and "b""#.to_owned())
+		);
+		assert!(StrictTokenizer::tokenize(&ParamType::String, r#""\x""#).is_err());
+		assert!(StrictTokenizer::tokenize(&ParamType::String, r#""\u{d800}""#).is_err());
+	}
+
 	#[test]
 	fn tokenize_int() {
 		assert_eq!(
 			StrictTokenizer::tokenize(
 				&ParamType::Int(256),
-				"1111111111111111111111111111111111111111111111111111111111111111"
+				"0x1111111111111111111111111111111111111111111111111111111111111111"
 			)
 			.unwrap(),
 			Token::Int(Int::from_big_endian(&[0x11u8; 32]))
@@ -159,7 +289,7 @@ mod tests {
 		assert_eq!(
 			StrictTokenizer::tokenize(
 				&ParamType::Int(256),
-				"2222222222222222222222222222222222222222222222222222222222222222"
+				"0x2222222222222222222222222222222222222222222222222222222222222222"
 			)
 			.unwrap(),
 			Token::Int(Int::from_big_endian(&[0x22u8; 32]))
@@ -182,6 +312,14 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn tokenize_string_array_with_apostrophe() {
+		assert_eq!(
+			StrictTokenizer::tokenize(&ParamType::Array(Box::new(ParamType::String)), "[don't, x]").unwrap(),
+			Token::Array(vec![Token::String("don't".to_owned()), Token::String("x".to_owned())])
+		);
+	}
+
 	#[test]
 	fn tokenize_bool_array_of_arrays() {
 		assert_eq!(
@@ -13,9 +13,10 @@ use core::fmt;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use super::HumanReadable;
 #[cfg(not(feature = "std"))]
 use crate::no_std_prelude::*;
-use crate::{Address, Bytes, FixedBytes, Int, ParamType, Uint};
+use crate::{checksum_address, Address, Bytes, FixedBytes, Int, ParamType, Uint};
 
 /// Ethereum ABI params.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -76,7 +77,7 @@ impl fmt::Display for Token {
 		match *self {
 			Token::Bool(b) => write!(f, "{b}"),
 			Token::String(ref s) => write!(f, "{s}"),
-			Token::Address(ref a) => write!(f, "{a:x}"),
+			Token::Address(ref a) => f.write_str(&checksum_address(a, None)),
 			Token::Bytes(ref bytes) | Token::FixedBytes(ref bytes) => write!(f, "{}", hex::encode(bytes)),
 			Token::Uint(ref i) | Token::Int(ref i) => write!(f, "{i:x}"),
 			Token::Array(ref arr) | Token::FixedArray(ref arr) => {
@@ -94,6 +95,13 @@ impl fmt::Display for Token {
 }
 
 impl Token {
+	/// Returns a formatter for humans: ints in decimal with their sign, addresses checksummed, bytes `0x` prefixed
+	/// and strings quoted and escaped. Unlike `Display`, its output is unambiguous and parsed back by
+	/// `StrictTokenizer` and `LenientTokenizer`.
+	pub fn human_readable(&self) -> HumanReadable<'_> {
+		HumanReadable(self)
+	}
+
 	/// Check whether the type of the token matches the given parameter type.
 	///
 	/// Numeric types (`Int` and `Uint`) type check if the size of the token
@@ -10,8 +10,12 @@
 
 #[cfg(not(feature = "std"))]
 use crate::no_std_prelude::*;
-use crate::{param_type::Writer, ParamType};
+#[cfg(feature = "serde")]
+use crate::param_type::Writer;
+use crate::{InternalType, ParamType};
+#[cfg(feature = "serde")]
 use core::fmt;
+#[cfg(feature = "serde")]
 use serde::{
 	de::{Error, MapAccess, Visitor},
 	ser::SerializeMap,
@@ -29,8 +33,19 @@ pub struct TupleParam {
 
 	/// Additional Internal type.
 	pub internal_type: Option<String>,
+
+	/// Components of the tuple in `kind`, if it is or contains one, keeping their names and internal types.
+	pub components: Vec<TupleParam>,
+}
+
+impl TupleParam {
+	/// Parses `internal_type`, telling structs, enums, contracts and user defined value types apart.
+	pub fn internal_type(&self) -> Option<InternalType> {
+		self.internal_type.as_deref().map(InternalType::parse)
+	}
 }
 
+#[cfg(feature = "serde")]
 impl<'a> Deserialize<'a> for TupleParam {
 	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 	where
@@ -40,8 +55,10 @@ impl<'a> Deserialize<'a> for TupleParam {
 	}
 }
 
+#[cfg(feature = "serde")]
 struct TupleParamVisitor;
 
+#[cfg(feature = "serde")]
 impl<'a> Visitor<'a> for TupleParamVisitor {
 	type Value = TupleParam;
 
@@ -90,11 +107,12 @@ impl<'a> Visitor<'a> for TupleParamVisitor {
 		}
 
 		let mut kind = kind.ok_or_else(|| Error::missing_field("kind"))?;
-		crate::param::set_tuple_components(&mut kind, components)?;
-		Ok(TupleParam { name, kind, internal_type })
+		let components = crate::param::set_tuple_components(&mut kind, components)?;
+		Ok(TupleParam { name, kind, internal_type, components })
 	}
 }
 
+#[cfg(feature = "serde")]
 impl Serialize for TupleParam {
 	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where
@@ -108,20 +126,17 @@ impl Serialize for TupleParam {
 			map.serialize_entry("name", name)?;
 		}
 		map.serialize_entry("type", &Writer::write_for_abi(&self.kind, false))?;
-		if let Some(inner_tuple) = crate::param::inner_tuple(&self.kind) {
-			map.serialize_key("components")?;
-			map.serialize_value(&crate::param::SerializeableParamVec(inner_tuple))?;
-		}
+		crate::param::serialize_components(&mut map, &self.kind, &self.components)?;
 		map.end()
 	}
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "serde"))]
 mod tests {
 	#[cfg(not(feature = "std"))]
 	use crate::no_std_prelude::*;
 	use crate::{
-		tests::{assert_json_eq, assert_ser_de},
+		tests::{assert_json_eq, assert_ser_de, component},
 		ParamType, TupleParam,
 	};
 
@@ -136,7 +151,12 @@ mod tests {
 
 		assert_eq!(
 			deserialized,
-			TupleParam { name: Some("foo".to_owned()), kind: ParamType::Address, internal_type: None }
+			TupleParam {
+				name: Some("foo".to_owned()),
+				kind: ParamType::Address,
+				internal_type: None,
+				components: vec![]
+			}
 		);
 
 		assert_json_eq(s, serde_json::to_string(&deserialized).unwrap().as_str());
@@ -157,7 +177,8 @@ mod tests {
 			TupleParam {
 				name: Some("foo".to_owned()),
 				kind: ParamType::Address,
-				internal_type: Some("struct Verifier.Proof".to_string())
+				internal_type: Some("struct Verifier.Proof".to_string()),
+				components: vec![]
 			}
 		);
 
@@ -172,7 +193,10 @@ mod tests {
 
 		let deserialized: TupleParam = serde_json::from_str(s).unwrap();
 
-		assert_eq!(deserialized, TupleParam { name: None, kind: ParamType::Address, internal_type: None });
+		assert_eq!(
+			deserialized,
+			TupleParam { name: None, kind: ParamType::Address, internal_type: None, components: vec![] }
+		);
 
 		assert_json_eq(s, serde_json::to_string(&deserialized).unwrap().as_str());
 	}
@@ -203,7 +227,11 @@ mod tests {
 			TupleParam {
 				name: None,
 				kind: ParamType::Tuple(vec![ParamType::Uint(48), ParamType::Tuple(vec![ParamType::Address])]),
-				internal_type: None
+				internal_type: None,
+				components: vec![
+					component(ParamType::Uint(48), vec![]),
+					component(ParamType::Tuple(vec![ParamType::Address]), vec![component(ParamType::Address, vec![])]),
+				]
 			}
 		);
 
@@ -239,7 +267,26 @@ mod tests {
 			TupleParam {
 				name: None,
 				kind: ParamType::Tuple(vec![ParamType::Uint(48), ParamType::Tuple(vec![ParamType::Address])]),
-				internal_type: None
+				internal_type: None,
+				components: vec![
+					TupleParam {
+						name: Some("amount".to_owned()),
+						kind: ParamType::Uint(48),
+						internal_type: None,
+						components: vec![],
+					},
+					TupleParam {
+						name: Some("things".to_owned()),
+						kind: ParamType::Tuple(vec![ParamType::Address]),
+						internal_type: None,
+						components: vec![TupleParam {
+							name: Some("baseTupleParam".to_owned()),
+							kind: ParamType::Address,
+							internal_type: None,
+							components: vec![],
+						}],
+					},
+				]
 			}
 		);
 
@@ -274,7 +321,12 @@ mod tests {
 					ParamType::Address,
 					ParamType::Address
 				]))),
-				internal_type: None
+				internal_type: None,
+				components: vec![
+					component(ParamType::Uint(48), vec![]),
+					component(ParamType::Address, vec![]),
+					component(ParamType::Address, vec![]),
+				]
 			}
 		);
 
@@ -304,7 +356,8 @@ mod tests {
 					ParamType::Uint(8),
 					ParamType::Uint(16),
 				]))))),
-				internal_type: None
+				internal_type: None,
+				components: vec![component(ParamType::Uint(8), vec![]), component(ParamType::Uint(16), vec![])]
 			}
 		);
 
@@ -338,7 +391,12 @@ mod tests {
 					Box::new(ParamType::Tuple(vec![ParamType::Uint(48), ParamType::Address, ParamType::Address])),
 					2
 				),
-				internal_type: None
+				internal_type: None,
+				components: vec![
+					component(ParamType::Uint(48), vec![]),
+					component(ParamType::Address, vec![]),
+					component(ParamType::Address, vec![]),
+				]
 			}
 		);
 
@@ -379,7 +437,17 @@ mod tests {
 					ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::Address]))),
 					ParamType::FixedArray(Box::new(ParamType::Tuple(vec![ParamType::Address])), 42,)
 				]),
-				internal_type: None
+				internal_type: None,
+				components: vec![
+					component(
+						ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::Address]))),
+						vec![component(ParamType::Address, vec![])],
+					),
+					component(
+						ParamType::FixedArray(Box::new(ParamType::Tuple(vec![ParamType::Address])), 42),
+						vec![component(ParamType::Address, vec![])],
+					),
+				]
 			}
 		);
 
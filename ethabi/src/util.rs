@@ -8,7 +8,9 @@
 
 //! Utils used by different modules.
 
-use crate::Word;
+#[cfg(not(feature = "std"))]
+use crate::no_std_prelude::*;
+use crate::{Int, Word};
 
 /// Converts a u32 to a right aligned array of 32 bytes.
 pub fn pad_u32(value: u32) -> Word {
@@ -17,6 +19,15 @@ pub fn pad_u32(value: u32) -> Word {
 	padded
 }
 
+/// Formats a 256-bit two's complement integer as signed decimal.
+pub fn signed_decimal(value: &Int) -> String {
+	if value.bit(255) {
+		format!("-{}", (!*value).overflowing_add(Int::one()).0)
+	} else {
+		value.to_string()
+	}
+}
+
 // This is a workaround to support non-spec compliant function and event names,
 // see: https://github.com/paritytech/parity/issues/4122
 #[cfg(feature = "serde")]
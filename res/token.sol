@@ -0,0 +1,33 @@
+pragma solidity ^0.8.0;
+
+/// @title Token
+/// @author Parity Technologies
+/// @notice Keeps balances of a fixed supply token.
+contract Token {
+	/// @notice Emitted when tokens are moved.
+	/// @param from Sender.
+	/// @param to Recipient.
+	/// @param value Amount moved.
+	event Transfer(address indexed from, address indexed to, uint256 value);
+
+	/// @notice Balance of an account.
+	mapping(address => uint256) public balanceOf;
+
+	/// @notice Mints the whole supply to the deployer.
+	/// @param supply Total supply.
+	constructor(uint256 supply) {
+		balanceOf[msg.sender] = supply;
+	}
+
+	/// @notice Moves tokens to another account.
+	/// @dev Reverts if the sender balance is too low.
+	/// @param to Recipient.
+	/// @param value Amount to move.
+	/// @return success Always true.
+	function transfer(address to, uint256 value) public returns (bool success) {
+		balanceOf[msg.sender] -= value;
+		balanceOf[to] += value;
+		emit Transfer(msg.sender, to, value);
+		return true;
+	}
+}
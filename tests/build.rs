@@ -0,0 +1,20 @@
+// Copyright 2015-2019 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::{env, path::Path};
+
+fn main() {
+	println!("cargo:rerun-if-changed=../res/eip20.abi");
+
+	let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+	ethabi_codegen::Abigen::new("eip20_generated", "../res/eip20.abi")
+		.generate()
+		.expect("eip20.abi is valid")
+		.write_to_file(Path::new(&out_dir).join("eip20_generated.rs"))
+		.expect("OUT_DIR is writable");
+}
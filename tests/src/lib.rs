@@ -12,10 +12,44 @@ use_contract!(validators, "../res/Validators.abi");
 use_contract!(operations, "../res/Operations.abi");
 use_contract!(urlhint, "../res/urlhint.abi");
 use_contract!(test_rust_keywords, "../res/test_rust_keywords.abi");
+use_contract!(erc721, "../res/erc721.abi");
+use_contract!(counter, "../res/Counter.json");
+use_contract!(token, "../res/token.json", contract = "token.sol:Token");
+use_contract!(overloads_by_signature, "../res/test.abi", overloads = "signature");
+use_contract!(overloads_renamed, "../res/test.abi", rename(signature = "bar(string)", name = "bar_with_string"));
+use_contract!(
+	eip20_transfers,
+	"../res/eip20.abi",
+	functions("transfer", "balanceOf"),
+	skip_events("Approval"),
+	visibility = "pub(crate)",
+	types(address = "crate::Account", uint256 = "u128"),
+	serde = true
+);
+
+include!(concat!(env!("OUT_DIR"), "/eip20_generated.rs"));
+
+/// Account address, used in place of `ethabi::Address` by `eip20_transfers`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Account(pub [u8; 20]);
+
+impl From<Account> for ethabi::Address {
+	fn from(account: Account) -> Self {
+		account.0.into()
+	}
+}
+
+impl From<ethabi::Address> for Account {
+	fn from(address: ethabi::Address) -> Self {
+		Account(address.0)
+	}
+}
 
 #[cfg(test)]
 mod tests {
-	use crate::{eip20, validators};
+	use crate::{
+		counter, eip20, eip20_transfers, erc721, overloads_by_signature, overloads_renamed, token, validators, Account,
+	};
 	use ethabi::{Address, Uint};
 	use hex_literal::hex;
 
@@ -97,6 +131,153 @@ mod tests {
 		assert_eq!(expected_string, hex::encode(encoded_from_string))
 	}
 
+	#[test]
+	fn decoding_function_input() {
+		use eip20::functions;
+
+		let owner = [0u8; 20];
+		let spender = [1u8; 20];
+		let encoded = functions::allowance::encode_input(owner, spender);
+
+		let decoded = functions::allowance::decode_input(&encoded).unwrap();
+		assert_eq!(decoded, functions::allowance::Inputs { owner: owner.into(), spender: spender.into() });
+
+		// selector of a different function
+		assert!(functions::balance_of::decode_input(&encoded).is_err());
+		assert!(functions::allowance::decode_input(&encoded[..3]).is_err());
+	}
+
+	#[test]
+	fn decoding_function_input_as_fixed_array() {
+		use validators::functions;
+
+		let first = [0x11u8; 20];
+		let second = [0x22u8; 20];
+		let encoded = functions::add_two_validators::encode_input([first, second]);
+
+		let decoded = functions::add_two_validators::decode_input(&encoded).unwrap();
+		assert_eq!(decoded.new_validators, [first.into(), second.into()]);
+	}
+
+	#[test]
+	fn decoding_call() {
+		use eip20::functions;
+
+		let to = [2u8; 20];
+		let encoded = functions::transfer::encode_input(to, 1000);
+
+		assert_eq!(
+			functions::decode_call(&encoded).unwrap(),
+			functions::Call::Transfer(functions::transfer::Inputs { to: to.into(), value: 1000.into() })
+		);
+		assert_eq!(
+			functions::decode_call(&functions::total_supply::encode_input()).unwrap(),
+			functions::Call::TotalSupply(functions::total_supply::Inputs {})
+		);
+		assert!(functions::decode_call(&hex!("deadbeef")).is_err());
+		assert!(functions::decode_call(&[]).is_err());
+	}
+
+	#[test]
+	fn overloaded_functions_by_arity() {
+		use erc721::functions;
+
+		let from = [1u8; 20];
+		let to = [2u8; 20];
+
+		let encoded = functions::safe_transfer_from_3::encode_input(from, to, 7);
+		assert_eq!(encoded[..4], hex!("42842e0e"));
+		let encoded = functions::safe_transfer_from_4::encode_input(from, to, 7, vec![0xff]);
+		assert_eq!(encoded[..4], hex!("b88d4fde"));
+
+		assert_eq!(
+			functions::decode_call(&encoded).unwrap(),
+			functions::Call::SafeTransferFrom4(functions::safe_transfer_from_4::Inputs {
+				from: from.into(),
+				to: to.into(),
+				token_id: 7.into(),
+				data: vec![0xff],
+			})
+		);
+	}
+
+	#[test]
+	fn overloaded_functions_by_signature() {
+		use overloads_by_signature::functions;
+
+		assert_eq!(functions::foo::encode_input(true)[..4], hex!("45557578"));
+		assert_eq!(functions::bar_bool::encode_input(true)[..4], hex!("6fae9412"));
+		assert_eq!(functions::bar_string::encode_input("baz")[..4], hex!("d473a8ed"));
+	}
+
+	#[test]
+	fn renamed_overloaded_function() {
+		use overloads_renamed::functions;
+
+		assert_eq!(functions::bar::encode_input(true)[..4], hex!("6fae9412"));
+		assert_eq!(functions::bar_with_string::encode_input("baz")[..4], hex!("d473a8ed"));
+	}
+
+	#[test]
+	fn custom_types() {
+		use eip20_transfers::{events, functions, logs};
+
+		let to = Account([2u8; 20]);
+		let encoded = functions::transfer::encode_input(to, 1000u128);
+		assert_eq!(encoded, eip20::functions::transfer::encode_input(to.0, 1000));
+		assert_eq!(
+			functions::decode_call(&encoded).unwrap(),
+			functions::Call::Transfer(functions::transfer::Inputs { to, value: 1000 })
+		);
+
+		// does not fit into `u128`
+		let encoded = eip20::functions::transfer::encode_input(to.0, ethabi::Uint::MAX);
+		assert!(functions::transfer::decode_input(&encoded).is_err());
+
+		let log = ethabi::RawLog {
+			topics: vec![
+				events::transfer::event().signature(),
+				hex!("0000000000000000000000000101010101010101010101010101010101010101").into(),
+				hex!("0000000000000000000000000202020202020202020202020202020202020202").into(),
+			],
+			data: hex!("00000000000000000000000000000000000000000000000000000000000003e8").into(),
+		};
+		let transfer = events::transfer::parse_log(log).unwrap();
+		assert_eq!(transfer, logs::Transfer { from: Account([1u8; 20]), to, value: 1000 });
+
+		let json = serde_json::to_string(&transfer).unwrap();
+		assert_eq!(serde_json::from_str::<logs::Transfer>(&json).unwrap(), transfer);
+	}
+
+	#[test]
+	fn generated_bindings() {
+		let to = [2u8; 20];
+
+		assert_eq!(
+			crate::eip20_generated::functions::transfer::encode_input(to, 1000),
+			eip20::functions::transfer::encode_input(to, 1000)
+		);
+		assert_eq!(crate::eip20_generated::events::transfer::event(), eip20::events::transfer::event());
+	}
+
+	#[test]
+	fn deploying_artifact() {
+		let bytecode = hex!("608060405234801561001057600080fd5b50");
+		assert_eq!(counter::BYTECODE, bytecode);
+
+		let mut expected = bytecode.to_vec();
+		expected.extend_from_slice(&hex!("0000000000000000000000000000000000000000000000000000000000000005"));
+		assert_eq!(counter::deploy(5), expected);
+		assert_eq!(counter::functions::number::encode_input(), hex!("8381f58a"));
+	}
+
+	#[test]
+	fn deploying_standard_json_contract() {
+		assert_eq!(token::BYTECODE, hex!("608060405234801561001057600080fd5b50"));
+		assert_eq!(&token::deploy(1)[..token::BYTECODE.len()], token::BYTECODE);
+		assert_eq!(token::functions::transfer::encode_input([0u8; 20], 1)[..4], hex!("a9059cbb"));
+	}
+
 	#[test]
 	fn encoding_input_works() {
 		let expected = "dd62ed3e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000101010101010101010101010101010101010101".to_owned();
@@ -11,4 +11,28 @@ for abi in *.abi; do
 	cat tmp > "$abi"
 done
 
+# standard json output, with bytecode and NatSpec, of token.sol
+solc --standard-json --allow-paths . > tmp <<INPUT
+{
+	"language": "Solidity",
+	"sources": { "token.sol": { "urls": ["token.sol"] } },
+	"settings": {
+		"outputSelection": {
+			"*": {
+				"*": [
+					"abi",
+					"evm.bytecode.object",
+					"evm.deployedBytecode.object",
+					"evm.methodIdentifiers",
+					"metadata",
+					"devdoc",
+					"userdoc"
+				]
+			}
+		}
+	}
+}
+INPUT
+python -m json.tool tmp > token.json
+
 rm tmp